@@ -0,0 +1,285 @@
+//! Authenticated admin endpoints.
+//!
+//! Enabled only when `ADMIN_TOKEN` is set; requests must send
+//! `Authorization: Bearer <ADMIN_TOKEN>`.
+//!
+//! - `GET /admin/log-level`: current filter directives
+//! - `PUT /admin/log-level` with `{"directives": "debug,h2=info", "ttl_secs": 300}`:
+//!   replace the directives, reverting after `ttl_secs` when given
+
+use std::env;
+use std::time::Duration;
+
+use actix_web::http::header;
+use actix_web::{get, put, web, HttpRequest, HttpResponse};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::telemetry::LogLevelHandle;
+
+/// State shared by the admin endpoints
+pub struct AdminState {
+    token: String,
+    log_level: LogLevelHandle,
+}
+
+impl AdminState {
+    pub fn new(token: impl Into<String>, log_level: LogLevelHandle) -> Self {
+        Self {
+            token: token.into(),
+            log_level,
+        }
+    }
+
+    /// Read `ADMIN_TOKEN`; `None` (admin endpoints disabled) if unset or empty
+    pub fn from_env(log_level: LogLevelHandle) -> Option<Self> {
+        env::var("ADMIN_TOKEN")
+            .ok()
+            .filter(|token| !token.is_empty())
+            .map(|token| Self::new(token, log_level))
+    }
+
+    fn is_authorized(&self, request: &HttpRequest) -> bool {
+        request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| constant_time_eq(token.as_bytes(), self.token.as_bytes()))
+    }
+}
+
+#[derive(Deserialize)]
+struct LogLevelRequest {
+    directives: String,
+    ttl_secs: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct LogLevelResponse {
+    directives: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    revert_after_secs: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// Register the admin endpoints, if enabled
+pub fn configure(cfg: &mut web::ServiceConfig, state: Option<web::Data<AdminState>>) {
+    if let Some(state) = state {
+        cfg.app_data(state)
+            .service(get_log_level)
+            .service(put_log_level);
+    }
+}
+
+#[get("/admin/log-level")]
+async fn get_log_level(request: HttpRequest, state: web::Data<AdminState>) -> HttpResponse {
+    if !state.is_authorized(&request) {
+        return unauthorized();
+    }
+
+    HttpResponse::Ok().json(LogLevelResponse {
+        directives: state.log_level.current(),
+        revert_after_secs: None,
+    })
+}
+
+#[put("/admin/log-level")]
+async fn put_log_level(
+    request: HttpRequest,
+    state: web::Data<AdminState>,
+    body: web::Bytes,
+) -> HttpResponse {
+    if !state.is_authorized(&request) {
+        warn!("Rejected unauthorized log level change");
+        return unauthorized();
+    }
+
+    // Parsed only after the token check, so unauthenticated callers can't probe the schema
+    let body: LogLevelRequest = match serde_json::from_slice(&body) {
+        Ok(body) => body,
+        Err(e) => return bad_request(e),
+    };
+
+    let result = match body.ttl_secs {
+        Some(ttl) => state
+            .log_level
+            .set_for(&body.directives, Duration::from_secs(ttl)),
+        None => state.log_level.set(&body.directives),
+    };
+
+    match result {
+        Ok(()) => {
+            info!(directives = %body.directives, ttl_secs = body.ttl_secs, "Log level changed");
+            HttpResponse::Ok().json(LogLevelResponse {
+                directives: state.log_level.current(),
+                revert_after_secs: body.ttl_secs,
+            })
+        }
+        Err(e) => bad_request(e),
+    }
+}
+
+fn bad_request(error: impl std::fmt::Display) -> HttpResponse {
+    HttpResponse::BadRequest().json(ErrorResponse {
+        error: error.to_string(),
+    })
+}
+
+fn unauthorized() -> HttpResponse {
+    HttpResponse::Unauthorized()
+        .insert_header((header::WWW_AUTHENTICATE, "Bearer"))
+        .finish()
+}
+
+/// Compare without leaking the position of the first difference through timing
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use actix_web::test::{call_service, init_service, read_body_json, TestRequest};
+    use actix_web::App;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::EnvFilter;
+
+    const TOKEN: &str = "s3cret";
+
+    /// Admin state over a reloadable filter installed on a non-global registry
+    fn state() -> (impl tracing::Subscriber, web::Data<AdminState>) {
+        let (layer, log_level) = LogLevelHandle::reloadable(EnvFilter::new("info"));
+        let subscriber = tracing_subscriber::registry().with(layer);
+        (
+            subscriber,
+            web::Data::new(AdminState::new(TOKEN, log_level)),
+        )
+    }
+
+    fn put(token: &str, body: serde_json::Value) -> TestRequest {
+        TestRequest::put()
+            .uri("/admin/log-level")
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+            .set_json(body)
+    }
+
+    #[tokio::test]
+    async fn put_changes_log_level() {
+        let (_subscriber, state) = state();
+        let app =
+            init_service(App::new().configure(|cfg| configure(cfg, Some(state.clone())))).await;
+
+        let response = call_service(
+            &app,
+            put(TOKEN, serde_json::json!({"directives": "debug"})).to_request(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value = read_body_json(response).await;
+        assert_eq!(body, serde_json::json!({"directives": "debug"}));
+        assert_eq!(state.log_level.current(), "debug");
+    }
+
+    #[tokio::test]
+    async fn put_with_ttl_reports_revert() {
+        let (_subscriber, state) = state();
+        let app =
+            init_service(App::new().configure(|cfg| configure(cfg, Some(state.clone())))).await;
+
+        let request = put(
+            TOKEN,
+            serde_json::json!({"directives": "trace", "ttl_secs": 300}),
+        );
+        let body: serde_json::Value =
+            read_body_json(call_service(&app, request.to_request()).await).await;
+
+        assert_eq!(
+            body,
+            serde_json::json!({"directives": "trace", "revert_after_secs": 300})
+        );
+    }
+
+    #[tokio::test]
+    async fn put_rejects_wrong_token() {
+        let (_subscriber, state) = state();
+        let app =
+            init_service(App::new().configure(|cfg| configure(cfg, Some(state.clone())))).await;
+
+        let response = call_service(
+            &app,
+            put("guess", serde_json::json!({"directives": "debug"})).to_request(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(state.log_level.current(), "info");
+    }
+
+    #[tokio::test]
+    async fn put_checks_token_before_body() {
+        let (_subscriber, state) = state();
+        let app =
+            init_service(App::new().configure(|cfg| configure(cfg, Some(state.clone())))).await;
+
+        let unauthenticated = TestRequest::put()
+            .uri("/admin/log-level")
+            .set_payload("not json")
+            .to_request();
+        let malformed = TestRequest::put()
+            .uri("/admin/log-level")
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", TOKEN)))
+            .set_payload("not json")
+            .to_request();
+
+        assert_eq!(
+            call_service(&app, unauthenticated).await.status(),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            call_service(&app, malformed).await.status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[tokio::test]
+    async fn put_rejects_invalid_directives() {
+        let (_subscriber, state) = state();
+        let app =
+            init_service(App::new().configure(|cfg| configure(cfg, Some(state.clone())))).await;
+
+        let response = call_service(
+            &app,
+            put(TOKEN, serde_json::json!({"directives": "x=loud"})).to_request(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn disabled_without_token() {
+        let app = init_service(App::new().configure(|cfg| configure(cfg, None))).await;
+
+        let response = call_service(
+            &app,
+            TestRequest::get().uri("/admin/log-level").to_request(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn compares_tokens() {
+        assert!(constant_time_eq(b"abc", b"abc"));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"abcd"));
+    }
+}
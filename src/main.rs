@@ -1,8 +1,9 @@
+mod admin;
 mod telemetry;
-use actix_web::{get, web, App, HttpResponse, HttpServer, Responder};
+use actix_web::{get, middleware, web, App, HttpResponse, HttpServer, Responder};
 use serde::Deserialize;
 use std::env;
-use tracing::info;
+use tracing::{error, info};
 use tracing_actix_web::TracingLogger;
 
 #[derive(Deserialize)]
@@ -24,27 +25,122 @@ async fn health() -> impl Responder {
     HttpResponse::Ok().body("ok")
 }
 
+/// Resolve once SIGTERM (sent by Cloud Run before shutdown) or SIGINT arrives
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install SIGINT handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => info!("Received SIGINT"),
+        _ = terminate => info!("Received SIGTERM"),
+    }
+}
+
+async fn init_telemetry() -> Result<telemetry::TelemetryGuard, telemetry::TelemetryError> {
+    let mut config = telemetry::TelemetryConfig::load()?;
+    config
+        .failure_policy
+        .get_or_insert(telemetry::FailurePolicy::FallbackToLocal);
+    telemetry::init_with_detection(config).await
+}
+
 #[tokio::main]
 async fn main() -> std::io::Result<()> {
-    // Initialize telemetry (auto-detects GCP from GOOGLE_CLOUD_PROJECT env var)
-    telemetry::init()
-        .await
-        .expect("Failed to initialize telemetry");
+    // Initialize telemetry (auto-detects GCP from GOOGLE_CLOUD_PROJECT env var).
+    // Serve with local telemetry if the backend can't start, unless
+    // TELEMETRY_FAILURE_POLICY or the config file asks for something else.
+    let telemetry = match init_telemetry().await {
+        Ok(telemetry) => telemetry,
+        Err(e) => {
+            eprintln!("Failed to initialize telemetry: {}", e);
+            std::process::exit(1);
+        }
+    };
 
     let port: u16 = env::var("PORT")
         .unwrap_or_else(|_| "8080".to_string())
         .parse()
         .expect("PORT must be a number");
 
+    // Admin endpoints are only mounted when ADMIN_TOKEN is set
+    let admin = telemetry
+        .log_level()
+        .cloned()
+        .and_then(admin::AdminState::from_env)
+        .map(web::Data::new);
+
     info!("Starting server on port {}", port);
 
-    HttpServer::new(|| {
+    let server = HttpServer::new(move || {
         App::new()
+            .wrap(middleware::from_fn(telemetry::trace_response))
             .wrap(TracingLogger::default())
             .service(hello)
             .service(health)
+            .configure(|cfg| admin::configure(cfg, admin.clone()))
     })
+    .disable_signals()
     .bind(("0.0.0.0", port))?
-    .run()
-    .await
+    .run();
+
+    // Drain in-flight requests first so their spans end before telemetry is flushed
+    let handle = server.handle();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        info!("Draining HTTP server");
+        handle.stop(true).await;
+    });
+
+    server.await?;
+
+    if let Err(e) = telemetry.shutdown().await {
+        error!(error = %e, "Failed to flush telemetry");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::{call_service, init_service, read_body, TestRequest};
+    use telemetry::testing::{assert_attribute, assert_parent, InMemoryProvider};
+
+    #[tokio::test]
+    async fn hello_records_user_on_handler_span() {
+        let telemetry = InMemoryProvider::new();
+        let _guard = telemetry.set_default();
+        let app = init_service(
+            App::new()
+                .wrap(TracingLogger::default())
+                .service(hello),
+        )
+        .await;
+
+        let response = call_service(&app, TestRequest::get().uri("/?user=ada").to_request()).await;
+
+        assert!(response.status().is_success());
+        // The root span ends once the response body has been sent
+        assert_eq!(read_body(response).await, "Hello, ada!");
+        let root = telemetry.span("GET /");
+        let handler = telemetry.span("hello");
+        assert_parent(&handler, &root);
+        assert_attribute(&handler, "user", "ada");
+        assert_attribute(&root, "http.status_code", 200_i64);
+        assert_eq!(telemetry.log_messages(), vec!["Hello endpoint called"]);
+    }
 }
@@ -1,7 +1,13 @@
+use opentelemetry_sdk::logs::SdkLoggerProvider;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
 use opentelemetry_sdk::trace::SdkTracerProvider;
 
 use crate::telemetry::config::{TelemetryBackend, TelemetryConfig};
 use crate::telemetry::error::TelemetryError;
+use crate::telemetry::fanout::collect_skipped_backends;
+use crate::telemetry::guard::TelemetryGuard;
+use crate::telemetry::metrics::init_meter_provider;
+use crate::telemetry::propagation::init_propagator;
 use crate::telemetry::trace::init_subscriber;
 
 /// Trait for telemetry providers (GCP, local, etc.)
@@ -11,20 +17,64 @@ pub trait TelemetryProvider: Send + Sync {
         &self,
         config: &TelemetryConfig,
     ) -> impl std::future::Future<Output = Result<SdkTracerProvider, TelemetryError>> + Send;
+
+    /// Build the meter provider for this backend
+    fn build_meter_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> impl std::future::Future<Output = Result<SdkMeterProvider, TelemetryError>> + Send;
+
+    /// Build the logger provider for this backend (only called when log export is enabled)
+    fn build_logger_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> impl std::future::Future<Output = Result<SdkLoggerProvider, TelemetryError>> + Send;
 }
 
 /// Initialize telemetry with a specific provider
+///
+/// Keep the returned guard alive for the lifetime of the process.
 pub async fn init_with_provider<P: TelemetryProvider>(
     provider: &P,
     config: &TelemetryConfig,
-) -> Result<(), TelemetryError> {
-    let tracer_provider = provider.build_tracer_provider(config).await?;
-    init_subscriber(tracer_provider, config);
-    Ok(())
+) -> Result<TelemetryGuard, TelemetryError> {
+    let (tracer_provider, skipped) =
+        collect_skipped_backends(provider.build_tracer_provider(config)).await;
+    let tracer_provider = tracer_provider?;
+    let meter_provider = provider.build_meter_provider(config).await?;
+    let logger_provider = if config.export_logs {
+        Some(provider.build_logger_provider(config).await?)
+    } else {
+        None
+    };
+
+    init_propagator();
+    let log_level = init_subscriber(tracer_provider.clone(), logger_provider.as_ref(), config);
+    init_meter_provider(meter_provider.clone());
+    for backend in &skipped {
+        backend.report();
+    }
+
+    let guard = TelemetryGuard::new(tracer_provider)
+        .with_meter_provider(meter_provider)
+        .with_log_level(log_level);
+    Ok(match logger_provider {
+        Some(logger_provider) => guard.with_logger_provider(logger_provider),
+        None => guard,
+    })
 }
 
 /// Initialize telemetry with config (uses backend from config)
-pub async fn init_with_config(config: &TelemetryConfig) -> Result<(), TelemetryError> {
+///
+/// Fails with [`TelemetryError::InvalidConfig`] before installing anything if
+/// [`TelemetryConfig::validate`] finds problems. If the backend itself fails to
+/// start, `config.failure_policy` decides between returning the error and
+/// falling back to local telemetry (see [`crate::telemetry::fallback`]).
+pub async fn init_with_config(
+    config: &TelemetryConfig,
+) -> Result<TelemetryGuard, TelemetryError> {
+    config.validate()?;
+
     match &config.backend {
         TelemetryBackend::Local => {
             let provider = crate::telemetry::default::DefaultProvider;
@@ -32,16 +82,57 @@ pub async fn init_with_config(config: &TelemetryConfig) -> Result<(), TelemetryE
         }
         #[cfg(feature = "telemetry-gcp")]
         TelemetryBackend::Gcp(gcp_config) => {
-            let provider = crate::telemetry::gcp::GcpProvider::new(gcp_config.clone());
-            init_with_provider(&provider, config).await
+            crate::telemetry::fallback::init_gcp(gcp_config, config).await
+        }
+        #[cfg(feature = "telemetry-aws")]
+        TelemetryBackend::Aws(aws_config) => {
+            let provider = crate::telemetry::aws::AwsProvider::new(aws_config.clone());
+            crate::telemetry::fallback::init_or_fallback(&provider, config, Some(&aws_config.endpoint))
+                .await
+                .map(|(guard, _)| guard)
+        }
+        #[cfg(feature = "telemetry-zipkin")]
+        TelemetryBackend::Zipkin(zipkin_config) => {
+            let provider = crate::telemetry::zipkin::ZipkinProvider::new(zipkin_config.clone());
+            crate::telemetry::fallback::init_or_fallback(&provider, config, None)
+                .await
+                .map(|(guard, _)| guard)
+        }
+        TelemetryBackend::Custom(custom) => {
+            crate::telemetry::fallback::init_or_fallback(custom, config, None)
+                .await
+                .map(|(guard, _)| guard)
         }
     }
 }
 
-/// Initialize telemetry from environment (Local backend)
-pub async fn init() -> Result<(), TelemetryError> {
-    let config = TelemetryConfig::from_env();
-    init_with_config(&config).await
+/// Initialize telemetry from the config file and environment
+///
+/// See [`TelemetryConfig::load`]. Unless the config file names a backend
+/// (`backend = "local"` included), it is detected with
+/// [`TelemetryBackend::detect`], so on GCP the project ID may come from the
+/// metadata server.
+pub async fn init() -> Result<TelemetryGuard, TelemetryError> {
+    init_with_detection(TelemetryConfig::load()?).await
+}
+
+/// Initialize telemetry with `config`, detecting the backend as [`init`] does
+///
+/// For binaries that adjust the loaded config first, e.g. to pick their own
+/// [`FailurePolicy`](crate::telemetry::config::FailurePolicy) default.
+pub async fn init_with_detection(
+    config: TelemetryConfig,
+) -> Result<TelemetryGuard, TelemetryError> {
+    init_with_config(&with_detected_backend(config).await).await
+}
+
+/// `config` with its backend detected, unless it was chosen. Env-selected
+/// backends are detected again, so a GCP one gets its platform checked.
+async fn with_detected_backend(mut config: TelemetryConfig) -> TelemetryConfig {
+    if !config.backend_selected() {
+        config.backend = TelemetryBackend::detect().await;
+    }
+    config
 }
 
 #[cfg(test)]
@@ -59,4 +150,97 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn init_with_provider_builds_logger() {
+        use crate::telemetry::default::DefaultProvider;
+
+        let provider = DefaultProvider;
+        let config = TelemetryConfig::new("test", "1.0").with_export_logs(true);
+
+        let result = provider.build_logger_provider(&config).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn init_with_provider_builds_meter() {
+        use crate::telemetry::default::DefaultProvider;
+
+        let provider = DefaultProvider;
+        let config = TelemetryConfig::new("test", "1.0");
+
+        let result = provider.build_meter_provider(&config).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn init_with_config_rejects_invalid_config() {
+        let config = TelemetryConfig::new("test", "1.0").with_otlp_endpoint("invalid-url");
+
+        let result = init_with_config(&config).await;
+
+        assert!(matches!(result, Err(TelemetryError::InvalidConfig(problems)) if problems.len() == 1));
+    }
+
+    struct FailingProvider;
+
+    impl TelemetryProvider for FailingProvider {
+        async fn build_tracer_provider(
+            &self,
+            _config: &TelemetryConfig,
+        ) -> Result<SdkTracerProvider, TelemetryError> {
+            Err(TelemetryError::Exporter("collector unreachable".into()))
+        }
+
+        async fn build_meter_provider(
+            &self,
+            _config: &TelemetryConfig,
+        ) -> Result<SdkMeterProvider, TelemetryError> {
+            unreachable!()
+        }
+
+        async fn build_logger_provider(
+            &self,
+            _config: &TelemetryConfig,
+        ) -> Result<SdkLoggerProvider, TelemetryError> {
+            unreachable!()
+        }
+    }
+
+    #[tokio::test]
+    async fn init_with_config_uses_custom_backend() {
+        use crate::telemetry::registry::CustomBackend;
+
+        let config = TelemetryConfig::new("test", "1.0")
+            .with_backend(TelemetryBackend::Custom(CustomBackend::new("failing", FailingProvider)));
+
+        let result = init_with_config(&config).await;
+
+        assert!(matches!(result, Err(TelemetryError::Exporter(_))));
+    }
+
+    #[cfg(feature = "telemetry-gcp")]
+    #[tokio::test]
+    async fn init_detects_platform_of_env_selected_gcp_backend() {
+        use crate::telemetry::testing::EnvGuard;
+
+        let _guard = EnvGuard::new(&[
+            "TELEMETRY_CONFIG_FILE",
+            "GOOGLE_CLOUD_PROJECT",
+            "KUBERNETES_SERVICE_HOST",
+        ]);
+        std::env::set_var("GOOGLE_CLOUD_PROJECT", "gke-project");
+        std::env::set_var("KUBERNETES_SERVICE_HOST", "10.0.0.1");
+
+        let config = with_detected_backend(TelemetryConfig::load().unwrap()).await;
+
+        match config.backend {
+            TelemetryBackend::Gcp(gcp_config) => {
+                assert_eq!(gcp_config.platform.as_str(), "gcp_kubernetes_engine")
+            }
+            other => panic!("expected a GCP backend, got {:?}", other),
+        }
+    }
 }
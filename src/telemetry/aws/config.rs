@@ -0,0 +1,231 @@
+use std::env;
+
+use serde::Deserialize;
+
+use crate::telemetry::config::check_endpoint;
+use crate::telemetry::error::ConfigProblem;
+
+/// OTLP endpoint of an ADOT collector running alongside the service
+/// (sidecar, daemon or Lambda extension)
+pub const DEFAULT_ENDPOINT: &str = "http://localhost:4317";
+
+/// AWS compute platforms (maps to cloud.platform semconv values)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AwsPlatform {
+    #[default]
+    Ec2,
+    Ecs,
+    Eks,
+    Lambda,
+}
+
+impl AwsPlatform {
+    /// Returns the OpenTelemetry semantic convention value
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ec2 => "aws_ec2",
+            Self::Ecs => "aws_ecs",
+            Self::Eks => "aws_eks",
+            Self::Lambda => "aws_lambda",
+        }
+    }
+
+    /// Detect Lambda and ECS from environment variables.
+    ///
+    /// EC2 and EKS set no such variables; select them in config.
+    pub fn detect() -> Option<Self> {
+        Self::detect_with(|name| env::var(name).ok())
+    }
+
+    fn detect_with(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        if var("AWS_LAMBDA_FUNCTION_NAME").is_some() {
+            Some(Self::Lambda)
+        } else if var("ECS_CONTAINER_METADATA_URI_V4").is_some()
+            || var("ECS_CONTAINER_METADATA_URI").is_some()
+        {
+            Some(Self::Ecs)
+        } else {
+            None
+        }
+    }
+}
+
+/// AWS-specific configuration
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AwsConfig {
+    /// Region, e.g. `us-east-1`; on ECS it can also come from task metadata
+    #[serde(default)]
+    pub region: Option<String>,
+    /// ADOT collector OTLP endpoint
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+    #[serde(default)]
+    pub platform: AwsPlatform,
+}
+
+fn default_endpoint() -> String {
+    DEFAULT_ENDPOINT.to_string()
+}
+
+impl Default for AwsConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AwsConfig {
+    pub fn new() -> Self {
+        Self {
+            region: None,
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            platform: AwsPlatform::default(),
+        }
+    }
+
+    pub fn with_region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
+    pub fn with_platform(mut self, platform: AwsPlatform) -> Self {
+        self.platform = platform;
+        self
+    }
+
+    /// Record problems with the region and endpoint
+    pub fn validate(&self, problems: &mut Vec<ConfigProblem>) {
+        if let Some(region) = self
+            .region
+            .as_deref()
+            .filter(|region| !is_valid_region(region))
+        {
+            problems.push(ConfigProblem::new(
+                "aws.region",
+                format!("{:?} is not a region name (e.g. us-east-1)", region),
+            ));
+        }
+        check_endpoint("aws.endpoint", &self.endpoint, problems);
+    }
+
+    /// Create from environment variables, if running on Lambda or ECS
+    /// - AWS_REGION / AWS_DEFAULT_REGION for region
+    /// - OTEL_EXPORTER_OTLP_ENDPOINT for endpoint (defaults to DEFAULT_ENDPOINT)
+    /// - Platform detected from AWS_LAMBDA_FUNCTION_NAME or the ECS metadata URI
+    pub fn from_env() -> Option<Self> {
+        AwsPlatform::detect()
+            .map(|platform| Self::new().with_platform(platform).with_env_overrides())
+    }
+
+    /// Replace the region, endpoint and platform with any the environment sets
+    pub fn with_env_overrides(mut self) -> Self {
+        if let Ok(region) = env::var("AWS_REGION").or_else(|_| env::var("AWS_DEFAULT_REGION")) {
+            self.region = Some(region);
+        }
+        if let Ok(endpoint) = env::var("OTEL_EXPORTER_OTLP_ENDPOINT") {
+            self.endpoint = endpoint;
+        }
+        if let Some(platform) = AwsPlatform::detect() {
+            self.platform = platform;
+        }
+        self
+    }
+}
+
+/// Region name format, e.g. `us-east-1` or `us-gov-west-1`
+fn is_valid_region(region: &str) -> bool {
+    let parts: Vec<&str> = region.split('-').collect();
+
+    parts.len() >= 3
+        && parts.iter().all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        })
+        && parts
+            .last()
+            .is_some_and(|part| part.chars().all(|c| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn detect(vars: &[(&str, &str)]) -> Option<AwsPlatform> {
+        let vars: HashMap<&str, &str> = vars.iter().copied().collect();
+        AwsPlatform::detect_with(|name| vars.get(name).map(|value| value.to_string()))
+    }
+
+    #[test]
+    fn detects_lambda_and_ecs() {
+        assert_eq!(
+            detect(&[("AWS_LAMBDA_FUNCTION_NAME", "orders")]),
+            Some(AwsPlatform::Lambda)
+        );
+        assert_eq!(
+            detect(&[(
+                "ECS_CONTAINER_METADATA_URI_V4",
+                "http://169.254.170.2/v4/abc"
+            )]),
+            Some(AwsPlatform::Ecs)
+        );
+        assert_eq!(
+            detect(&[("ECS_CONTAINER_METADATA_URI", "http://169.254.170.2/v3/abc")]),
+            Some(AwsPlatform::Ecs)
+        );
+        assert_eq!(detect(&[("AWS_REGION", "us-east-1")]), None);
+    }
+
+    #[test]
+    fn aws_platform_as_str_returns_semconv_values() {
+        assert_eq!(AwsPlatform::Ec2.as_str(), "aws_ec2");
+        assert_eq!(AwsPlatform::Ecs.as_str(), "aws_ecs");
+        assert_eq!(AwsPlatform::Eks.as_str(), "aws_eks");
+        assert_eq!(AwsPlatform::Lambda.as_str(), "aws_lambda");
+    }
+
+    #[test]
+    fn aws_config_new_uses_defaults() {
+        let config = AwsConfig::new();
+
+        assert_eq!(config.region, None);
+        assert_eq!(config.endpoint, DEFAULT_ENDPOINT);
+        assert_eq!(config.platform, AwsPlatform::Ec2);
+    }
+
+    #[test]
+    fn validates_region_format() {
+        for region in ["us-east-1", "eu-central-2", "us-gov-west-1"] {
+            assert!(is_valid_region(region), "{}", region);
+        }
+        for region in ["", "us-east", "US-EAST-1", "us_east_1", "us--1"] {
+            assert!(!is_valid_region(region), "{}", region);
+        }
+    }
+
+    #[test]
+    fn validate_reports_region_and_endpoint() {
+        let mut problems = Vec::new();
+        AwsConfig::new()
+            .with_region("Virginia")
+            .with_endpoint("localhost:4317")
+            .validate(&mut problems);
+
+        let fields: Vec<_> = problems.iter().map(|problem| problem.field).collect();
+        assert_eq!(fields, vec!["aws.region", "aws.endpoint"]);
+
+        let mut problems = Vec::new();
+        AwsConfig::new()
+            .with_region("us-west-2")
+            .validate(&mut problems);
+        assert!(problems.is_empty());
+    }
+}
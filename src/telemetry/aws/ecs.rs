@@ -0,0 +1,199 @@
+use std::env;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::telemetry::error::TelemetryError;
+
+/// Upper bound for one lookup; the endpoint is link-local, so keep it short
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Client for the ECS task metadata endpoint (v4, or v3 on older agents).
+///
+/// Lookups never fail: anything other than a valid answer reads as `None`.
+#[derive(Debug, Clone)]
+pub struct EcsMetadataClient {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+/// Task fields from `{base}/task`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EcsTask {
+    #[serde(rename = "Cluster")]
+    pub cluster_arn: String,
+    #[serde(rename = "TaskARN")]
+    pub task_arn: String,
+    pub family: String,
+    pub revision: String,
+    #[serde(default)]
+    pub availability_zone: Option<String>,
+    /// `FARGATE` or `EC2` (v4 only)
+    #[serde(default)]
+    pub launch_type: Option<String>,
+}
+
+/// Container fields from `{base}`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EcsContainer {
+    pub name: String,
+    #[serde(default)]
+    pub docker_id: Option<String>,
+    /// v4 only
+    #[serde(default, rename = "ContainerARN")]
+    pub container_arn: Option<String>,
+    #[serde(default)]
+    pub log_options: Option<LogOptions>,
+}
+
+/// `awslogs` driver settings of the container
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct LogOptions {
+    #[serde(default, rename = "awslogs-group")]
+    pub group: Option<String>,
+    #[serde(default, rename = "awslogs-stream")]
+    pub stream: Option<String>,
+}
+
+impl EcsMetadataClient {
+    /// Client for a metadata base URL, e.g. the value of `ECS_CONTAINER_METADATA_URI_V4`
+    pub fn new(base_url: impl Into<String>) -> Result<Self, TelemetryError> {
+        let client = reqwest::Client::builder()
+            .timeout(DEFAULT_TIMEOUT)
+            .build()
+            .map_err(|e| {
+                TelemetryError::Init(format!("Failed to build ECS metadata client: {}", e))
+            })?;
+
+        Ok(Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client,
+        })
+    }
+
+    /// Client for `ECS_CONTAINER_METADATA_URI_V4` (or `ECS_CONTAINER_METADATA_URI`), if set
+    pub fn from_env() -> Option<Self> {
+        let base_url = env::var("ECS_CONTAINER_METADATA_URI_V4")
+            .or_else(|_| env::var("ECS_CONTAINER_METADATA_URI"))
+            .ok()?;
+        Self::new(base_url).ok()
+    }
+
+    pub async fn task(&self) -> Option<EcsTask> {
+        self.get(&format!("{}/task", self.base_url)).await
+    }
+
+    pub async fn container(&self) -> Option<EcsContainer> {
+        self.get(&self.base_url).await
+    }
+
+    async fn get<T: DeserializeOwned>(&self, url: &str) -> Option<T> {
+        let response = match self.client.get(url).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                tracing::debug!(error = %e, "ECS metadata endpoint unreachable");
+                return None;
+            }
+        };
+        if !response.status().is_success() {
+            return None;
+        }
+        serde_json::from_str(&response.text().await.ok()?).ok()
+    }
+}
+
+/// Region and account ID of an ARN (`arn:aws:service:region:account:resource`)
+pub fn arn_region_and_account(arn: &str) -> Option<(String, String)> {
+    let mut parts = arn.splitn(6, ':');
+    if parts.next()? != "arn" {
+        return None;
+    }
+    let (_partition, _service) = (parts.next()?, parts.next()?);
+    let region = parts.next().filter(|region| !region.is_empty())?;
+    let account = parts.next().filter(|account| !account.is_empty())?;
+    Some((region.to_string(), account.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const TASK_ARN: &str =
+        "arn:aws:ecs:us-west-2:111122223333:task/default/158d1c8083dd49d6b527399fd6414f5c";
+
+    async fn metadata_stub() -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v4/abc/task"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Cluster": "arn:aws:ecs:us-west-2:111122223333:cluster/default",
+                "TaskARN": TASK_ARN,
+                "Family": "orders",
+                "Revision": "26",
+                "AvailabilityZone": "us-west-2d",
+                "LaunchType": "FARGATE",
+                "Containers": []
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v4/abc"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "DockerId": "cd189a933e5849daa93386466019ab50-2495160603",
+                "Name": "orders-api",
+                "ContainerARN": "arn:aws:ecs:us-west-2:111122223333:container/05966557",
+                "LogDriver": "awslogs",
+                "LogOptions": {
+                    "awslogs-group": "/ecs/orders",
+                    "awslogs-region": "us-west-2",
+                    "awslogs-stream": "ecs/orders-api/158d1c8083dd49d6b527399fd6414f5c"
+                }
+            })))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn reads_task_and_container() {
+        let server = metadata_stub().await;
+        let client = EcsMetadataClient::new(format!("{}/v4/abc", server.uri())).unwrap();
+
+        let task = client.task().await.unwrap();
+        assert_eq!(task.task_arn, TASK_ARN);
+        assert_eq!(task.family, "orders");
+        assert_eq!(task.availability_zone.as_deref(), Some("us-west-2d"));
+        assert_eq!(task.launch_type.as_deref(), Some("FARGATE"));
+
+        let container = client.container().await.unwrap();
+        assert_eq!(container.name, "orders-api");
+        assert_eq!(
+            container.log_options.unwrap().group.as_deref(),
+            Some("/ecs/orders")
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_endpoint_reads_as_none() {
+        let server = MockServer::start().await;
+        let client = EcsMetadataClient::new(format!("{}/v4/abc", server.uri())).unwrap();
+
+        assert_eq!(client.task().await, None);
+        assert_eq!(client.container().await, None);
+    }
+
+    #[test]
+    fn parses_arn_region_and_account() {
+        assert_eq!(
+            arn_region_and_account(TASK_ARN),
+            Some(("us-west-2".to_string(), "111122223333".to_string()))
+        );
+        assert_eq!(arn_region_and_account("arn:aws:s3:::bucket"), None);
+        assert_eq!(arn_region_and_account("not-an-arn"), None);
+    }
+}
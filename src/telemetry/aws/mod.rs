@@ -0,0 +1,245 @@
+//! AWS X-Ray telemetry provider.
+//!
+//! This module exports traces, metrics and logs over OTLP to an AWS Distro
+//! for OpenTelemetry (ADOT) collector running alongside the service, which
+//! forwards them to X-Ray and CloudWatch with its own credentials.
+//!
+//! # Features
+//!
+//! - X-Ray compatible trace IDs ([`XrayIdGenerator`])
+//! - `X-Amzn-Trace-Id` propagation, so request spans join the load balancer's
+//!   or API Gateway's trace
+//! - Semantic conventions for AWS resource attributes, including Lambda
+//!   function settings and ECS task metadata
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use telemetry::aws::{AwsConfig, AwsPlatform};
+//!
+//! // Collector on the default endpoint
+//! let config = AwsConfig::new().with_region("us-east-1");
+//!
+//! // With a collector elsewhere
+//! let config = AwsConfig::new()
+//!     .with_platform(AwsPlatform::Eks)
+//!     .with_endpoint("http://adot-collector.observability:4317");
+//!
+//! // From environment variables (Lambda and ECS only)
+//! let config = AwsConfig::from_env().expect("AWS config from env");
+//! ```
+//!
+//! # Environment Variables
+//!
+//! - `AWS_REGION` / `AWS_DEFAULT_REGION`: Region
+//! - `OTEL_EXPORTER_OTLP_ENDPOINT`: ADOT collector endpoint (default `http://localhost:4317`)
+//! - `AWS_LAMBDA_FUNCTION_NAME`: Lambda detection; with `AWS_LAMBDA_FUNCTION_{VERSION,MEMORY_SIZE}`
+//!   and `AWS_LAMBDA_LOG_{GROUP,STREAM}_NAME` for function attributes
+//! - `ECS_CONTAINER_METADATA_URI_V4` / `ECS_CONTAINER_METADATA_URI`: ECS detection,
+//!   and the task metadata endpoint for cluster, task and container attributes
+
+pub mod config;
+pub mod ecs;
+pub mod propagator;
+pub mod resource;
+pub mod xray;
+
+use opentelemetry_otlp::SpanExporter;
+use opentelemetry_sdk::logs::SdkLoggerProvider;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+
+use crate::telemetry::api::TelemetryProvider;
+use crate::telemetry::config::TelemetryConfig;
+use crate::telemetry::default::exporter;
+use crate::telemetry::error::TelemetryError;
+use crate::telemetry::fanout::with_secondary_backends;
+use crate::telemetry::logs::build_batch_logger_provider;
+use crate::telemetry::metrics::build_periodic_meter_provider;
+
+pub use config::{AwsConfig, AwsPlatform};
+pub use ecs::EcsMetadataClient;
+pub use propagator::XrayPropagator;
+pub use resource::{AwsResourceBuilder, LambdaFunction};
+pub use xray::XrayIdGenerator;
+
+/// AWS X-Ray telemetry provider.
+///
+/// Exports to the ADOT collector at `AwsConfig::endpoint` using the
+/// configured OTLP protocol, headers and TLS settings.
+pub struct AwsProvider {
+    config: AwsConfig,
+    ecs: Option<EcsMetadataClient>,
+}
+
+impl AwsProvider {
+    /// Create a new AWS provider with the given configuration.
+    ///
+    /// On ECS, resource attributes are read from the task metadata endpoint.
+    pub fn new(config: AwsConfig) -> Self {
+        Self {
+            config,
+            ecs: EcsMetadataClient::from_env(),
+        }
+    }
+
+    /// Use this task metadata client (or none) for resource detection
+    pub fn with_ecs_metadata(mut self, ecs: Option<EcsMetadataClient>) -> Self {
+        self.ecs = ecs;
+        self
+    }
+
+    /// Span exporter to the collector
+    pub(crate) fn span_exporter(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SpanExporter, TelemetryError> {
+        exporter::build_span_exporter(&self.config.endpoint, config)
+    }
+
+    pub(crate) async fn resource(&self, config: &TelemetryConfig) -> Resource {
+        let mut builder = AwsResourceBuilder::new(self.config.platform, self.config.region.clone());
+        if let Some(ecs) = &self.ecs {
+            builder = builder.with_ecs_metadata(ecs).await;
+        }
+        builder.build(config)
+    }
+}
+
+impl TelemetryProvider for AwsProvider {
+    async fn build_tracer_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkTracerProvider, TelemetryError> {
+        let exporter = self.span_exporter(config)?;
+
+        let builder = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_sampler(config.sampler.to_sampler())
+            .with_id_generator(XrayIdGenerator::new())
+            .with_resource(self.resource(config).await);
+
+        Ok(with_secondary_backends(builder, config).await.build())
+    }
+
+    async fn build_meter_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkMeterProvider, TelemetryError> {
+        let exporter = exporter::build_metric_exporter(&self.config.endpoint, config)?;
+
+        Ok(build_periodic_meter_provider(
+            exporter,
+            self.resource(config).await,
+        ))
+    }
+
+    async fn build_logger_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkLoggerProvider, TelemetryError> {
+        let exporter = exporter::build_log_exporter(&self.config.endpoint, config)?;
+
+        Ok(build_batch_logger_provider(
+            exporter,
+            self.resource(config).await,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::aws::xray::trace_id_timestamp;
+    use crate::telemetry::config::OtlpProtocol;
+    use opentelemetry::trace::{TraceContextExt, Tracer, TracerProvider};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Stand-in for the ADOT collector's OTLP/HTTP receiver
+    async fn collector_stub() -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/traces"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn aws_provider_exports_xray_spans_to_collector() {
+        let server = collector_stub().await;
+        let provider = AwsProvider::new(
+            AwsConfig::new()
+                .with_region("us-east-1")
+                .with_endpoint(server.uri()),
+        )
+        .with_ecs_metadata(None);
+        let config =
+            TelemetryConfig::new("orders", "1.0.0").with_otlp_protocol(OtlpProtocol::HttpJson);
+
+        let tracer_provider = provider.build_tracer_provider(&config).await.unwrap();
+        let trace_id = tracer_provider
+            .tracer("test")
+            .in_span("checkout", |cx| cx.span().span_context().trace_id());
+        // The HTTP exporter blocks, so flush off the runtime's worker threads
+        tokio::task::spawn_blocking(move || tracer_provider.force_flush())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        let resource_spans = &body["resourceSpans"][0];
+        let span = &resource_spans["scopeSpans"][0]["spans"][0];
+        assert_eq!(span["traceId"], trace_id.to_string());
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as u32;
+        assert!(now - trace_id_timestamp(trace_id) < 60);
+
+        let attributes = resource_spans["resource"]["attributes"].as_array().unwrap();
+        let attribute = |key: &str| {
+            attributes
+                .iter()
+                .find(|attribute| attribute["key"] == key)
+                .map(|attribute| attribute["value"]["stringValue"].clone())
+        };
+        assert_eq!(attribute("cloud.provider"), Some("aws".into()));
+        assert_eq!(attribute("cloud.region"), Some("us-east-1".into()));
+    }
+
+    #[tokio::test]
+    async fn aws_provider_reads_ecs_task_metadata() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v4/abc/task"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Cluster": "arn:aws:ecs:eu-west-1:111122223333:cluster/prod",
+                "TaskARN": "arn:aws:ecs:eu-west-1:111122223333:task/prod/158d",
+                "Family": "orders",
+                "Revision": "3"
+            })))
+            .mount(&server)
+            .await;
+        let ecs = EcsMetadataClient::new(format!("{}/v4/abc", server.uri())).unwrap();
+        let provider = AwsProvider::new(AwsConfig::new().with_platform(AwsPlatform::Ecs))
+            .with_ecs_metadata(Some(ecs));
+
+        let resource = provider
+            .resource(&TelemetryConfig::new("orders", "1.0.0"))
+            .await;
+
+        let get = |key: &'static str| resource.get(&opentelemetry::Key::from_static_str(key));
+        assert_eq!(get("cloud.platform"), Some("aws_ecs".into()));
+        assert_eq!(get("cloud.region"), Some("eu-west-1".into()));
+        assert_eq!(
+            get("aws.ecs.cluster.arn"),
+            Some("arn:aws:ecs:eu-west-1:111122223333:cluster/prod".into())
+        );
+    }
+}
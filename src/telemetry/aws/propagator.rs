@@ -0,0 +1,202 @@
+use std::sync::LazyLock;
+
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::propagation::{text_map_propagator::FieldIter, Extractor, Injector};
+use opentelemetry::trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState};
+use opentelemetry::Context;
+
+/// Header set by ALB, API Gateway, Lambda and other AWS services
+pub const XRAY_TRACE_HEADER: &str = "x-amzn-trace-id";
+
+static XRAY_TRACE_FIELDS: LazyLock<[String; 1]> = LazyLock::new(|| [XRAY_TRACE_HEADER.to_string()]);
+
+/// Propagator for the `X-Amzn-Trace-Id: Root=1-TIME-ID;Parent=SPAN_ID;Sampled=FLAG` format.
+///
+/// `TIME` is 8 hex characters and `ID` 24, which together form the trace ID;
+/// `SPAN_ID` is 16 hex characters. `Sampled=1` marks the trace as sampled;
+/// `0`, `?` or a missing flag mean not sampled. Other fields are ignored.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct XrayPropagator {
+    _private: (),
+}
+
+impl XrayPropagator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TextMapPropagator for XrayPropagator {
+    fn inject_context(&self, cx: &Context, injector: &mut dyn Injector) {
+        let span = cx.span();
+        let span_context = span.span_context();
+        if span_context.is_valid() {
+            injector.set(XRAY_TRACE_HEADER, format_header(span_context));
+        }
+    }
+
+    fn extract_with_context(&self, cx: &Context, extractor: &dyn Extractor) -> Context {
+        extractor
+            .get(XRAY_TRACE_HEADER)
+            .and_then(parse_header)
+            .map(|span_context| cx.with_remote_span_context(span_context))
+            .unwrap_or_else(|| cx.clone())
+    }
+
+    fn fields(&self) -> FieldIter<'_> {
+        FieldIter::new(XRAY_TRACE_FIELDS.as_ref())
+    }
+}
+
+/// Render a span context as an `X-Amzn-Trace-Id` value
+pub fn format_header(span_context: &SpanContext) -> String {
+    let trace_id = span_context.trace_id().to_string();
+    format!(
+        "Root=1-{}-{};Parent={};Sampled={}",
+        &trace_id[..8],
+        &trace_id[8..],
+        span_context.span_id(),
+        u8::from(span_context.is_sampled())
+    )
+}
+
+/// Parse an `X-Amzn-Trace-Id` value into a remote span context
+pub fn parse_header(value: &str) -> Option<SpanContext> {
+    let mut root = None;
+    let mut parent = None;
+    let mut sampled = false;
+
+    for field in value.split(';') {
+        match field.trim().split_once('=') {
+            Some(("Root", value)) => root = Some(value),
+            Some(("Parent", value)) => parent = Some(value),
+            Some(("Sampled", value)) => sampled = value == "1",
+            _ => {}
+        }
+    }
+
+    let trace_id = parse_root(root?)?;
+    let span_id = parent?;
+    if span_id.len() != 16 {
+        return None;
+    }
+    let span_id = SpanId::from(u64::from_str_radix(span_id, 16).ok()?);
+
+    let trace_flags = if sampled {
+        TraceFlags::SAMPLED
+    } else {
+        TraceFlags::default()
+    };
+
+    let span_context =
+        SpanContext::new(trace_id, span_id, trace_flags, true, TraceState::default());
+    span_context.is_valid().then_some(span_context)
+}
+
+/// `1-TIME-ID` to a trace ID
+fn parse_root(root: &str) -> Option<TraceId> {
+    let (time, id) = root.strip_prefix("1-")?.split_once('-')?;
+    if time.len() != 8 || id.len() != 24 {
+        return None;
+    }
+    let trace_id = u128::from_str_radix(&format!("{}{}", time, id), 16).ok()?;
+    Some(TraceId::from(trace_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const ROOT: &str = "1-5759e988-bd862e3fe1be46a994272793";
+    const TRACE_ID: &str = "5759e988bd862e3fe1be46a994272793";
+
+    fn extract(headers: &[(&str, &str)]) -> SpanContext {
+        let carrier: HashMap<String, String> = headers
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let cx = XrayPropagator::new().extract(&carrier);
+        cx.span().span_context().clone()
+    }
+
+    #[test]
+    fn parses_sampled_header() {
+        let sc = parse_header(&format!("Root={};Parent=53995c3f42cd8ad8;Sampled=1", ROOT)).unwrap();
+
+        assert_eq!(sc.trace_id().to_string(), TRACE_ID);
+        assert_eq!(sc.span_id().to_string(), "53995c3f42cd8ad8");
+        assert!(sc.is_sampled());
+        assert!(sc.is_remote());
+    }
+
+    #[test]
+    fn parses_unsampled_deferred_and_missing_flags() {
+        for sampled in [";Sampled=0", ";Sampled=?", ""] {
+            let header = format!("Root={};Parent=53995c3f42cd8ad8{}", ROOT, sampled);
+            assert!(!parse_header(&header).unwrap().is_sampled(), "{}", header);
+        }
+    }
+
+    #[test]
+    fn ignores_field_order_and_extra_fields() {
+        let sc = parse_header(&format!(
+            "Sampled=1; Lineage=a87bd80c:1 ;Parent=53995c3f42cd8ad8;Root={}",
+            ROOT
+        ))
+        .unwrap();
+
+        assert_eq!(sc.trace_id().to_string(), TRACE_ID);
+        assert!(sc.is_sampled());
+    }
+
+    #[test]
+    fn rejects_malformed_headers() {
+        assert!(parse_header("").is_none());
+        // Root without Parent (e.g. from a front end that starts the trace)
+        assert!(parse_header(&format!("Root={};Sampled=1", ROOT)).is_none());
+        assert!(
+            parse_header("Root=2-5759e988-bd862e3fe1be46a994272793;Parent=53995c3f42cd8ad8")
+                .is_none()
+        );
+        assert!(
+            parse_header("Root=1-5759e98-bd862e3fe1be46a994272793;Parent=53995c3f42cd8ad8")
+                .is_none()
+        );
+        assert!(parse_header(&format!("Root={};Parent=xyz", ROOT)).is_none());
+        assert!(parse_header(&format!("Root={};Parent=0000000000000000", ROOT)).is_none());
+    }
+
+    #[test]
+    fn format_round_trips() {
+        let header = format!("Root={};Parent=53995c3f42cd8ad8;Sampled=1", ROOT);
+
+        assert_eq!(format_header(&parse_header(&header).unwrap()), header);
+    }
+
+    #[test]
+    fn extract_reads_header() {
+        let sc = extract(&[(
+            XRAY_TRACE_HEADER,
+            &format!("Root={};Parent=53995c3f42cd8ad8;Sampled=1", ROOT),
+        )]);
+
+        assert_eq!(sc.trace_id().to_string(), TRACE_ID);
+    }
+
+    #[test]
+    fn extract_without_header_is_empty() {
+        assert!(!extract(&[]).is_valid());
+    }
+
+    #[test]
+    fn inject_writes_header() {
+        let header = format!("Root={};Parent=53995c3f42cd8ad8;Sampled=0", ROOT);
+        let cx = Context::new().with_remote_span_context(parse_header(&header).unwrap());
+        let mut carrier = HashMap::new();
+
+        XrayPropagator::new().inject_context(&cx, &mut carrier);
+
+        assert_eq!(carrier.get(XRAY_TRACE_HEADER).unwrap(), &header);
+    }
+}
@@ -0,0 +1,313 @@
+use std::env;
+
+use opentelemetry::{Array, KeyValue, StringValue, Value};
+use opentelemetry_sdk::Resource;
+use opentelemetry_semantic_conventions::resource::{
+    AWS_ECS_CLUSTER_ARN, AWS_ECS_CONTAINER_ARN, AWS_ECS_LAUNCHTYPE, AWS_ECS_TASK_ARN,
+    AWS_ECS_TASK_FAMILY, AWS_ECS_TASK_REVISION, AWS_LOG_GROUP_NAMES, AWS_LOG_STREAM_NAMES,
+    CLOUD_ACCOUNT_ID, CLOUD_AVAILABILITY_ZONE, CLOUD_PLATFORM, CLOUD_PROVIDER, CLOUD_REGION,
+    CONTAINER_ID, CONTAINER_NAME, FAAS_INSTANCE, FAAS_MAX_MEMORY, FAAS_NAME, FAAS_VERSION,
+};
+
+use crate::telemetry::aws::config::AwsPlatform;
+use crate::telemetry::aws::ecs::{
+    arn_region_and_account, EcsContainer, EcsMetadataClient, EcsTask,
+};
+use crate::telemetry::config::TelemetryConfig;
+use crate::telemetry::resource::build_resource;
+
+/// AWS cloud provider value (semconv)
+pub const CLOUD_PROVIDER_AWS: &str = "aws";
+
+/// Lambda function settings from the runtime environment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LambdaFunction {
+    pub name: String,
+    pub version: Option<String>,
+    pub memory_mb: Option<u64>,
+    pub log_group: Option<String>,
+    pub log_stream: Option<String>,
+}
+
+impl LambdaFunction {
+    /// Read `AWS_LAMBDA_FUNCTION_{NAME,VERSION,MEMORY_SIZE}` and `AWS_LAMBDA_LOG_{GROUP,STREAM}_NAME`
+    pub fn from_env() -> Option<Self> {
+        Self::from_vars(|name| env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        Some(Self {
+            name: var("AWS_LAMBDA_FUNCTION_NAME")?,
+            version: var("AWS_LAMBDA_FUNCTION_VERSION"),
+            memory_mb: var("AWS_LAMBDA_FUNCTION_MEMORY_SIZE").and_then(|size| size.parse().ok()),
+            log_group: var("AWS_LAMBDA_LOG_GROUP_NAME"),
+            log_stream: var("AWS_LAMBDA_LOG_STREAM_NAME"),
+        })
+    }
+}
+
+/// AWS-specific resource attributes
+pub struct AwsResourceBuilder {
+    platform: AwsPlatform,
+    region: Option<String>,
+    account_id: Option<String>,
+    availability_zone: Option<String>,
+    lambda: Option<LambdaFunction>,
+    ecs_task: Option<EcsTask>,
+    ecs_container: Option<EcsContainer>,
+}
+
+impl AwsResourceBuilder {
+    pub fn new(platform: AwsPlatform, region: Option<String>) -> Self {
+        Self {
+            platform,
+            region,
+            account_id: None,
+            availability_zone: None,
+            lambda: match platform {
+                AwsPlatform::Lambda => LambdaFunction::from_env(),
+                _ => None,
+            },
+            ecs_task: None,
+            ecs_container: None,
+        }
+    }
+
+    pub fn with_region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    pub fn with_account_id(mut self, account_id: impl Into<String>) -> Self {
+        self.account_id = Some(account_id.into());
+        self
+    }
+
+    pub fn with_availability_zone(mut self, zone: impl Into<String>) -> Self {
+        self.availability_zone = Some(zone.into());
+        self
+    }
+
+    pub fn with_lambda(mut self, lambda: LambdaFunction) -> Self {
+        self.lambda = Some(lambda);
+        self
+    }
+
+    /// Task and container (ECS), also supplying region, account and zone where not set
+    pub fn with_ecs(mut self, task: EcsTask, container: Option<EcsContainer>) -> Self {
+        if let Some((region, account_id)) = arn_region_and_account(&task.task_arn) {
+            self.region.get_or_insert(region);
+            self.account_id.get_or_insert(account_id);
+        }
+        if self.availability_zone.is_none() {
+            self.availability_zone = task.availability_zone.clone();
+        }
+        self.ecs_task = Some(task);
+        self.ecs_container = container;
+        self
+    }
+
+    /// Fill the ECS attributes from the task metadata endpoint, if it answers
+    pub async fn with_ecs_metadata(self, metadata: &EcsMetadataClient) -> Self {
+        match metadata.task().await {
+            Some(task) => {
+                let container = metadata.container().await;
+                self.with_ecs(task, container)
+            }
+            None => self,
+        }
+    }
+
+    pub fn build(self, config: &TelemetryConfig) -> Resource {
+        let mut attrs = vec![
+            KeyValue::new(CLOUD_PROVIDER, CLOUD_PROVIDER_AWS),
+            KeyValue::new(CLOUD_PLATFORM, self.platform.as_str()),
+        ];
+
+        if let Some(region) = self.region {
+            attrs.push(KeyValue::new(CLOUD_REGION, region));
+        }
+
+        if let Some(account_id) = self.account_id {
+            attrs.push(KeyValue::new(CLOUD_ACCOUNT_ID, account_id));
+        }
+
+        if let Some(zone) = self.availability_zone {
+            attrs.push(KeyValue::new(CLOUD_AVAILABILITY_ZONE, zone));
+        }
+
+        if let Some(lambda) = self.lambda {
+            attrs.push(KeyValue::new(FAAS_NAME, lambda.name));
+            if let Some(version) = lambda.version {
+                attrs.push(KeyValue::new(FAAS_VERSION, version));
+            }
+            if let Some(memory_mb) = lambda.memory_mb {
+                // Semconv wants bytes
+                attrs.push(KeyValue::new(
+                    FAAS_MAX_MEMORY,
+                    (memory_mb * 1024 * 1024) as i64,
+                ));
+            }
+            if let Some(log_group) = lambda.log_group {
+                attrs.push(string_array(AWS_LOG_GROUP_NAMES, log_group));
+            }
+            if let Some(log_stream) = lambda.log_stream {
+                attrs.push(KeyValue::new(FAAS_INSTANCE, log_stream));
+            }
+        }
+
+        if let Some(task) = self.ecs_task {
+            attrs.push(KeyValue::new(AWS_ECS_CLUSTER_ARN, task.cluster_arn));
+            attrs.push(KeyValue::new(AWS_ECS_TASK_ARN, task.task_arn));
+            attrs.push(KeyValue::new(AWS_ECS_TASK_FAMILY, task.family));
+            attrs.push(KeyValue::new(AWS_ECS_TASK_REVISION, task.revision));
+            if let Some(launch_type) = task.launch_type {
+                attrs.push(KeyValue::new(
+                    AWS_ECS_LAUNCHTYPE,
+                    launch_type.to_lowercase(),
+                ));
+            }
+        }
+
+        if let Some(container) = self.ecs_container {
+            attrs.push(KeyValue::new(CONTAINER_NAME, container.name));
+            if let Some(docker_id) = container.docker_id {
+                attrs.push(KeyValue::new(CONTAINER_ID, docker_id));
+            }
+            if let Some(container_arn) = container.container_arn {
+                attrs.push(KeyValue::new(AWS_ECS_CONTAINER_ARN, container_arn));
+            }
+            if let Some(log_options) = container.log_options {
+                if let Some(group) = log_options.group {
+                    attrs.push(string_array(AWS_LOG_GROUP_NAMES, group));
+                }
+                if let Some(stream) = log_options.stream {
+                    attrs.push(string_array(AWS_LOG_STREAM_NAMES, stream));
+                }
+            }
+        }
+
+        build_resource(config, attrs)
+    }
+}
+
+/// Single-element string array attribute
+fn string_array(key: &'static str, value: String) -> KeyValue {
+    KeyValue::new(
+        key,
+        Value::Array(Array::String(vec![StringValue::from(value)])),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::Key;
+    use std::collections::HashMap;
+
+    fn test_config() -> TelemetryConfig {
+        TelemetryConfig::new("test-service", "1.0.0")
+    }
+
+    fn get(resource: &Resource, key: &'static str) -> Option<Value> {
+        resource.get(&Key::from_static_str(key))
+    }
+
+    #[test]
+    fn aws_resource_builder_includes_base_attributes() {
+        let resource = AwsResourceBuilder::new(AwsPlatform::Ec2, Some("eu-west-1".into()))
+            .build(&test_config());
+
+        assert_eq!(get(&resource, CLOUD_PROVIDER), Some("aws".into()));
+        assert_eq!(get(&resource, CLOUD_PLATFORM), Some("aws_ec2".into()));
+        assert_eq!(get(&resource, CLOUD_REGION), Some("eu-west-1".into()));
+        assert_eq!(get(&resource, "service.name"), Some("test-service".into()));
+    }
+
+    #[test]
+    fn lambda_function_reads_runtime_variables() {
+        let vars: HashMap<&str, &str> = [
+            ("AWS_LAMBDA_FUNCTION_NAME", "orders"),
+            ("AWS_LAMBDA_FUNCTION_VERSION", "$LATEST"),
+            ("AWS_LAMBDA_FUNCTION_MEMORY_SIZE", "512"),
+            ("AWS_LAMBDA_LOG_GROUP_NAME", "/aws/lambda/orders"),
+            ("AWS_LAMBDA_LOG_STREAM_NAME", "2024/01/01/[$LATEST]abc"),
+        ]
+        .into_iter()
+        .collect();
+        let lambda =
+            LambdaFunction::from_vars(|name| vars.get(name).map(|v| v.to_string())).unwrap();
+
+        let resource = AwsResourceBuilder::new(AwsPlatform::Lambda, Some("us-east-1".into()))
+            .with_lambda(lambda)
+            .build(&test_config());
+
+        assert_eq!(get(&resource, FAAS_NAME), Some("orders".into()));
+        assert_eq!(get(&resource, FAAS_VERSION), Some("$LATEST".into()));
+        assert_eq!(
+            get(&resource, FAAS_MAX_MEMORY),
+            Some(Value::I64(512 * 1024 * 1024))
+        );
+        assert_eq!(
+            get(&resource, AWS_LOG_GROUP_NAMES),
+            Some(Value::Array(Array::String(vec![
+                "/aws/lambda/orders".into()
+            ])))
+        );
+        assert_eq!(
+            get(&resource, FAAS_INSTANCE),
+            Some("2024/01/01/[$LATEST]abc".into())
+        );
+    }
+
+    #[test]
+    fn lambda_function_requires_name() {
+        assert_eq!(LambdaFunction::from_vars(|_| None), None);
+    }
+
+    #[test]
+    fn ecs_task_supplies_region_account_and_zone() {
+        let task = EcsTask {
+            cluster_arn: "arn:aws:ecs:us-west-2:111122223333:cluster/default".into(),
+            task_arn: "arn:aws:ecs:us-west-2:111122223333:task/default/158d".into(),
+            family: "orders".into(),
+            revision: "26".into(),
+            availability_zone: Some("us-west-2d".into()),
+            launch_type: Some("FARGATE".into()),
+        };
+
+        let resource = AwsResourceBuilder::new(AwsPlatform::Ecs, None)
+            .with_ecs(task, None)
+            .build(&test_config());
+
+        assert_eq!(get(&resource, CLOUD_REGION), Some("us-west-2".into()));
+        assert_eq!(
+            get(&resource, CLOUD_ACCOUNT_ID),
+            Some("111122223333".into())
+        );
+        assert_eq!(
+            get(&resource, CLOUD_AVAILABILITY_ZONE),
+            Some("us-west-2d".into())
+        );
+        assert_eq!(get(&resource, AWS_ECS_TASK_FAMILY), Some("orders".into()));
+        assert_eq!(get(&resource, AWS_ECS_LAUNCHTYPE), Some("fargate".into()));
+    }
+
+    #[test]
+    fn explicit_region_wins_over_task_arn() {
+        let task = EcsTask {
+            cluster_arn: "arn:aws:ecs:us-west-2:111122223333:cluster/default".into(),
+            task_arn: "arn:aws:ecs:us-west-2:111122223333:task/default/158d".into(),
+            family: "orders".into(),
+            revision: "1".into(),
+            availability_zone: None,
+            launch_type: None,
+        };
+
+        let resource = AwsResourceBuilder::new(AwsPlatform::Ecs, Some("eu-central-1".into()))
+            .with_ecs(task, None)
+            .build(&test_config());
+
+        assert_eq!(get(&resource, CLOUD_REGION), Some("eu-central-1".into()));
+    }
+}
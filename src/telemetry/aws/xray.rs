@@ -0,0 +1,73 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use opentelemetry::trace::{SpanId, TraceId};
+use opentelemetry_sdk::trace::{IdGenerator, RandomIdGenerator};
+
+/// Trace IDs that X-Ray accepts: the first 4 bytes are the start time in
+/// Unix seconds, the remaining 12 are random.
+///
+/// X-Ray rejects spans whose trace ID is older than 30 days, so fully random
+/// IDs get dropped. Span IDs are plain random.
+#[derive(Debug, Default)]
+pub struct XrayIdGenerator {
+    random: RandomIdGenerator,
+}
+
+impl XrayIdGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IdGenerator for XrayIdGenerator {
+    fn new_trace_id(&self) -> TraceId {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as u32);
+
+        let mut bytes = self.random.new_trace_id().to_bytes();
+        bytes[..4].copy_from_slice(&seconds.to_be_bytes());
+        TraceId::from_bytes(bytes)
+    }
+
+    fn new_span_id(&self) -> SpanId {
+        self.random.new_span_id()
+    }
+}
+
+/// Start time (Unix seconds) encoded in an X-Ray trace ID
+pub fn trace_id_timestamp(trace_id: TraceId) -> u32 {
+    let bytes = trace_id.to_bytes();
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> u32 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as u32
+    }
+
+    #[test]
+    fn trace_id_starts_with_current_time() {
+        let before = now();
+        let trace_id = XrayIdGenerator::new().new_trace_id();
+        let after = now();
+
+        let timestamp = trace_id_timestamp(trace_id);
+        assert!((before..=after).contains(&timestamp));
+        assert_ne!(trace_id, TraceId::INVALID);
+    }
+
+    #[test]
+    fn trace_ids_differ_within_the_same_second() {
+        let generator = XrayIdGenerator::new();
+
+        assert_ne!(generator.new_trace_id(), generator.new_trace_id());
+        assert_ne!(generator.new_span_id(), SpanId::INVALID);
+    }
+}
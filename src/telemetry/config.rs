@@ -1,17 +1,148 @@
+use std::collections::BTreeMap;
 use std::env;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Deserializer};
+use tracing_subscriber::EnvFilter;
+
+use crate::telemetry::error::{ConfigProblem, TelemetryError};
+use crate::telemetry::fanout::SecondaryBackend;
+use crate::telemetry::otlp::{OtlpExportConfig, OtlpTlsConfig};
+use crate::telemetry::registry::CustomBackend;
+use crate::telemetry::resource::{default_detectors, parse_resource_attributes, ResourceDetector};
+use crate::telemetry::sampling::SamplerConfig;
 
 /// Log output format
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum LogFormat {
     /// Pretty human-readable format with colors (for local dev)
     #[default]
     Pretty,
     /// JSON structured format (for cloud environments)
     Json,
+    /// Cloud Logging structured JSON, linked to Cloud Trace
+    #[cfg(feature = "telemetry-gcp")]
+    GcpJson,
 }
 
-/// Telemetry backend selection
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+impl LogFormat {
+    /// Parse a `LOG_FORMAT` value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "pretty" => Some(Self::Pretty),
+            "json" => Some(Self::Json),
+            #[cfg(feature = "telemetry-gcp")]
+            "gcp_json" => Some(Self::GcpJson),
+            _ => None,
+        }
+    }
+}
+
+/// OTLP wire protocol (values of `OTEL_EXPORTER_OTLP_PROTOCOL`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum OtlpProtocol {
+    /// OTLP/gRPC, usually on port 4317
+    #[default]
+    #[serde(rename = "grpc")]
+    Grpc,
+    /// OTLP/HTTP with binary protobuf payloads, usually on port 4318
+    #[serde(rename = "http/protobuf")]
+    HttpProtobuf,
+    /// OTLP/HTTP with JSON payloads
+    #[serde(rename = "http/json")]
+    HttpJson,
+}
+
+impl OtlpProtocol {
+    /// Read `OTEL_EXPORTER_OTLP_PROTOCOL`, falling back to gRPC
+    pub fn from_env() -> Self {
+        env::var("OTEL_EXPORTER_OTLP_PROTOCOL")
+            .ok()
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "grpc" => Some(Self::Grpc),
+            "http/protobuf" => Some(Self::HttpProtobuf),
+            "http/json" => Some(Self::HttpJson),
+            _ => None,
+        }
+    }
+}
+
+/// Where the default provider sends spans (values of `OTEL_TRACES_EXPORTER`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TracesExporter {
+    /// OTLP to `otlp_endpoint`; spans are dropped when no endpoint is set
+    #[default]
+    Otlp,
+    /// Print finished spans to stdout, for local debugging
+    Console,
+    /// Drop every span
+    None,
+}
+
+impl TracesExporter {
+    /// Read `OTEL_TRACES_EXPORTER`, falling back to OTLP
+    pub fn from_env() -> Self {
+        env::var("OTEL_TRACES_EXPORTER")
+            .ok()
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "otlp" => Some(Self::Otlp),
+            "console" => Some(Self::Console),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
+}
+
+/// What `init_with_config` does when the backend can't be set up, e.g. when
+/// GCP credentials are missing (values of `TELEMETRY_FAILURE_POLICY`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailurePolicy {
+    /// Return the error
+    #[default]
+    FailFast,
+    /// Run with local telemetry (stdout logs, OTLP to `otlp_endpoint` if set)
+    /// and keep retrying the backend's trace exporter in the background
+    FallbackToLocal,
+    /// Keep retrying the backend's trace exporter in the background, dropping
+    /// spans until it attaches; logs still go to stdout
+    RetryInBackground,
+}
+
+impl FailurePolicy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "fail_fast" => Some(Self::FailFast),
+            "fallback_to_local" => Some(Self::FallbackToLocal),
+            "retry_in_background" => Some(Self::RetryInBackground),
+            _ => None,
+        }
+    }
+}
+
+/// Telemetry backend selection.
+///
+/// In config files `backend = "local"`, a `[backend.gcp]` table holding a
+/// [`GcpConfig`](crate::telemetry::gcp::GcpConfig), a `[backend.aws]` table
+/// holding an `AwsConfig`, a `[backend.zipkin]` table holding a `ZipkinConfig`,
+/// or `backend.custom = "<name>"`
+/// for a backend registered with [`register_backend`](crate::telemetry::registry::register_backend).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TelemetryBackend {
     /// Local development (OTLP to local collector or no-op)
     #[default]
@@ -19,31 +150,134 @@ pub enum TelemetryBackend {
     /// Google Cloud Platform (Cloud Trace)
     #[cfg(feature = "telemetry-gcp")]
     Gcp(crate::telemetry::gcp::GcpConfig),
+    /// AWS X-Ray through an ADOT collector
+    #[cfg(feature = "telemetry-aws")]
+    Aws(crate::telemetry::aws::AwsConfig),
+    /// Zipkin server (traces only)
+    #[cfg(feature = "telemetry-zipkin")]
+    Zipkin(crate::telemetry::zipkin::ZipkinConfig),
+    /// Provider supplied by another crate (see [`crate::telemetry::registry`])
+    Custom(CustomBackend),
 }
 
 impl TelemetryBackend {
     /// Auto-detect backend from environment variables
-    /// Returns GCP backend if GCP project is configured, otherwise Local
+    /// Returns the backend registered under `TELEMETRY_BACKEND` if set, Zipkin
+    /// backend if a Zipkin endpoint is set, GCP backend if GCP project is
+    /// configured, AWS backend on Lambda or ECS, otherwise Local
     pub fn from_env() -> Self {
+        if let Some(custom) = Self::custom_from_env() {
+            return Self::Custom(custom);
+        }
+        #[cfg(feature = "telemetry-zipkin")]
+        {
+            if let Some(zipkin_config) = crate::telemetry::zipkin::ZipkinConfig::from_env() {
+                return Self::Zipkin(zipkin_config);
+            }
+        }
         #[cfg(feature = "telemetry-gcp")]
         {
             if let Some(gcp_config) = crate::telemetry::gcp::GcpConfig::from_env() {
                 return Self::Gcp(gcp_config);
             }
         }
+        #[cfg(feature = "telemetry-aws")]
+        {
+            if let Some(aws_config) = crate::telemetry::aws::AwsConfig::from_env() {
+                return Self::Aws(aws_config);
+            }
+        }
         Self::Local
     }
+
+    /// Like [`from_env`](Self::from_env), but unless the environment selects
+    /// another backend, on GCP also asks the metadata server
+    /// (`GCE_METADATA_HOST`) for the project when no variable names one
+    pub async fn detect() -> Self {
+        let from_env = Self::from_env();
+        #[cfg(feature = "telemetry-gcp")]
+        if matches!(from_env, Self::Local | Self::Gcp(_)) {
+            use crate::telemetry::gcp::{metadata::MetadataClient, GcpConfig};
+
+            if let Ok(metadata) = MetadataClient::from_env() {
+                if let Some(gcp_config) = GcpConfig::detect(&metadata).await {
+                    return Self::Gcp(gcp_config);
+                }
+            }
+        }
+        from_env
+    }
+
+    /// Apply the variables on top: `TELEMETRY_BACKEND` selects that backend,
+    /// and a GCP, AWS or Zipkin backend takes any project, region, endpoint or
+    /// platform they set. `Local` is kept as chosen.
+    pub fn with_env_overrides(self) -> Self {
+        if let Some(custom) = Self::custom_from_env() {
+            return Self::Custom(custom);
+        }
+        match self {
+            Self::Local => Self::Local,
+            #[cfg(feature = "telemetry-gcp")]
+            Self::Gcp(gcp_config) => Self::Gcp(gcp_config.with_env_overrides()),
+            #[cfg(feature = "telemetry-aws")]
+            Self::Aws(aws_config) => Self::Aws(aws_config.with_env_overrides()),
+            #[cfg(feature = "telemetry-zipkin")]
+            Self::Zipkin(zipkin_config) => Self::Zipkin(zipkin_config.with_env_overrides()),
+            Self::Custom(custom) => Self::Custom(custom),
+        }
+    }
+
+    /// Registered backend named by `TELEMETRY_BACKEND` (unknown names are left
+    /// for [`TelemetryConfig::validate`] to report)
+    fn custom_from_env() -> Option<CustomBackend> {
+        env_parsed("TELEMETRY_BACKEND", |name| CustomBackend::named(name.trim()))
+    }
 }
 
-/// Main telemetry configuration
-#[derive(Debug, Clone)]
+/// Main telemetry configuration.
+///
+/// Deserializable from a config file (see [`TelemetryConfig::load`]); every
+/// field is optional there and defaults as in [`TelemetryConfig::default`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct TelemetryConfig {
     pub service_name: String,
     pub service_version: String,
     pub otlp_endpoint: Option<String>,
+    pub otlp_protocol: OtlpProtocol,
+    /// Headers, timeout and compression for every OTLP exporter
+    pub otlp_export: OtlpExportConfig,
+    /// Trace-only overrides of `otlp_export`
+    pub otlp_traces_export: OtlpExportConfig,
+    /// TLS / mTLS settings for OTLP exporters
+    pub otlp_tls: OtlpTlsConfig,
+    /// Span destination for the default provider
+    pub traces_exporter: TracesExporter,
     pub log_level: String,
     pub log_format: LogFormat,
+    /// Also export tracing events as OTLP logs (stdout output is unaffected)
+    pub export_logs: bool,
+    pub sampler: SamplerConfig,
+    /// Extra resource attributes (`OTEL_RESOURCE_ATTRIBUTES`); a table in config files
+    #[serde(deserialize_with = "deserialize_attributes")]
+    pub resource_attributes: Vec<(String, String)>,
+    /// Detectors run when the resource is built (not settable from files)
+    #[serde(skip)]
+    pub resource_detectors: Vec<Arc<dyn ResourceDetector>>,
+    /// Backend to use. Unless chosen with [`with_backend`](Self::with_backend),
+    /// the builder or a config file, [`init`](crate::telemetry::init) detects it.
     pub backend: TelemetryBackend,
+    /// Extra span destinations alongside `backend` (see [`crate::telemetry::fanout`])
+    pub secondary_backends: Vec<SecondaryBackend>,
+    /// Reaction to a backend that fails to initialize; unset means
+    /// [`FailurePolicy::FailFast`], leaving binaries free to pick their own default
+    pub failure_policy: Option<FailurePolicy>,
+    /// `TELEMETRY_BACKEND` value naming no registered backend, reported by `validate`
+    #[serde(skip)]
+    unregistered_backend: Option<String>,
+    /// Whether `backend` was chosen rather than taken from the environment
+    #[serde(skip)]
+    backend_selected: bool,
 }
 
 impl TelemetryConfig {
@@ -51,22 +285,130 @@ impl TelemetryConfig {
     /// - Detects GCP if GOOGLE_CLOUD_PROJECT is set
     /// - Falls back to Local backend otherwise
     pub fn from_env() -> Self {
-        let log_format = match env::var("LOG_FORMAT").as_deref() {
-            Ok("json") => LogFormat::Json,
-            Ok("pretty") => LogFormat::Pretty,
-            _ => LogFormat::Pretty,
+        Self::default().with_env_overrides()
+    }
+
+    /// Load config from the file named by `TELEMETRY_CONFIG_FILE`, if set,
+    /// then apply environment variables on top.
+    ///
+    /// Precedence, lowest first: defaults, the config file, environment
+    /// variables. Settings made in code afterwards (`with_*`) win over all.
+    pub fn load() -> Result<Self, TelemetryError> {
+        let config = match env::var_os("TELEMETRY_CONFIG_FILE") {
+            Some(path) => Self::from_file(path)?,
+            None => Self::default(),
         };
+        Ok(config.with_env_overrides())
+    }
 
-        Self {
-            service_name: env::var("OTEL_SERVICE_NAME")
-                .unwrap_or_else(|_| env!("CARGO_PKG_NAME").to_string()),
-            service_version: env::var("OTEL_SERVICE_VERSION")
-                .unwrap_or_else(|_| env!("CARGO_PKG_VERSION").to_string()),
-            otlp_endpoint: env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok(),
-            log_level: env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string()),
-            log_format,
-            backend: TelemetryBackend::from_env(),
+    /// Read a TOML (`.toml`) or YAML (`.yaml`, `.yml`, requires
+    /// `telemetry-yaml`) config file, ignoring the environment
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, TelemetryError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|e| {
+            TelemetryError::Config(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let invalid =
+            |e: &dyn std::fmt::Display| TelemetryError::Config(format!("{}: {}", path.display(), e));
+
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        let mut config: Self = match extension {
+            Some("toml") => toml::from_str(&contents).map_err(|e| invalid(&e))?,
+            // Enums as `{variant: value}` maps, matching the TOML layout, rather than YAML tags
+            #[cfg(feature = "telemetry-yaml")]
+            Some("yaml" | "yml") => serde_norway::with::singleton_map_recursive::deserialize(
+                serde_norway::Deserializer::from_str(&contents),
+            )
+            .map_err(|e| invalid(&e))?,
+            #[cfg(not(feature = "telemetry-yaml"))]
+            Some("yaml" | "yml") => {
+                return Err(TelemetryError::Config(format!(
+                    "{}: YAML config files require the telemetry-yaml feature",
+                    path.display()
+                )))
+            }
+            _ => {
+                return Err(TelemetryError::Config(format!(
+                    "{}: config files must end in .toml, .yaml or .yml",
+                    path.display()
+                )))
+            }
+        };
+
+        // Naming any backend, `local` included, turns off detection in `init`
+        let keys: Option<BackendKey> = match extension {
+            Some("toml") => toml::from_str(&contents).ok(),
+            #[cfg(feature = "telemetry-yaml")]
+            _ => serde_norway::from_str(&contents).ok(),
+            #[cfg(not(feature = "telemetry-yaml"))]
+            _ => None,
+        };
+        config.backend_selected = keys.is_some_and(|keys| keys.backend.is_some());
+        Ok(config)
+    }
+
+    /// Replace each setting whose environment variable is set, keeping the
+    /// others. OTLP exporter and TLS settings merge field by field;
+    /// `OTEL_RESOURCE_ATTRIBUTES` adds to `resource_attributes`.
+    pub fn with_env_overrides(mut self) -> Self {
+        let resource_attributes = env::var("OTEL_RESOURCE_ATTRIBUTES")
+            .map(|value| parse_resource_attributes(&value))
+            .unwrap_or_default();
+        let resource_attribute = |key: &str| {
+            resource_attributes
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.clone())
+        };
+
+        if let Some(name) = env::var("OTEL_SERVICE_NAME")
+            .ok()
+            .or_else(|| resource_attribute("service.name"))
+        {
+            self.service_name = name;
+        }
+        if let Some(version) = env::var("OTEL_SERVICE_VERSION")
+            .ok()
+            .or_else(|| resource_attribute("service.version"))
+        {
+            self.service_version = version;
+        }
+        if let Ok(endpoint) = env::var("OTEL_EXPORTER_OTLP_ENDPOINT") {
+            self.otlp_endpoint = Some(endpoint);
         }
+        if let Some(protocol) = env_parsed("OTEL_EXPORTER_OTLP_PROTOCOL", OtlpProtocol::parse) {
+            self.otlp_protocol = protocol;
+        }
+        self.otlp_export = OtlpExportConfig::from_env().or(&self.otlp_export);
+        self.otlp_traces_export = OtlpExportConfig::traces_from_env().or(&self.otlp_traces_export);
+        self.otlp_tls = self.otlp_tls.with_env_overrides();
+        if let Some(exporter) = env_parsed("OTEL_TRACES_EXPORTER", TracesExporter::parse) {
+            self.traces_exporter = exporter;
+        }
+        if let Some(policy) = env_parsed("TELEMETRY_FAILURE_POLICY", FailurePolicy::parse) {
+            self.failure_policy = Some(policy);
+        }
+        if let Ok(level) = env::var("RUST_LOG") {
+            self.log_level = level;
+        }
+        if let Some(format) = env_parsed("LOG_FORMAT", LogFormat::parse) {
+            self.log_format = format;
+        }
+        if let Ok(exporter) = env::var("OTEL_LOGS_EXPORTER") {
+            self.export_logs = exporter.trim() == "otlp";
+        }
+        if let Some(sampler) = SamplerConfig::from_env_if_set() {
+            self.sampler = sampler;
+        }
+        self.resource_attributes.extend(resource_attributes);
+        self.backend = if self.backend_selected {
+            self.backend.with_env_overrides()
+        } else {
+            TelemetryBackend::from_env()
+        };
+        self.unregistered_backend =
+            unregistered_backend(env::var("TELEMETRY_BACKEND").ok().as_deref());
+        self
     }
 
     /// Create a new config with explicit values
@@ -75,9 +417,22 @@ impl TelemetryConfig {
             service_name: service_name.into(),
             service_version: service_version.into(),
             otlp_endpoint: None,
+            otlp_protocol: OtlpProtocol::default(),
+            otlp_export: OtlpExportConfig::default(),
+            otlp_traces_export: OtlpExportConfig::default(),
+            otlp_tls: OtlpTlsConfig::default(),
+            traces_exporter: TracesExporter::default(),
             log_level: "info".to_string(),
             log_format: LogFormat::Pretty,
+            export_logs: false,
+            sampler: SamplerConfig::default(),
+            resource_attributes: Vec::new(),
+            resource_detectors: default_detectors(),
             backend: TelemetryBackend::Local,
+            secondary_backends: Vec::new(),
+            failure_policy: None,
+            unregistered_backend: None,
+            backend_selected: false,
         }
     }
 
@@ -92,6 +447,25 @@ impl TelemetryConfig {
 
     pub fn with_backend(mut self, backend: TelemetryBackend) -> Self {
         self.backend = backend;
+        self.unregistered_backend = None;
+        self.backend_selected = true;
+        self
+    }
+
+    /// Whether `backend` was chosen in code or a config file, rather than left
+    /// to the environment and detection
+    pub fn backend_selected(&self) -> bool {
+        self.backend_selected
+    }
+
+    /// Also send spans to `backend`
+    pub fn with_secondary_backend(mut self, backend: SecondaryBackend) -> Self {
+        self.secondary_backends.push(backend);
+        self
+    }
+
+    pub fn with_failure_policy(mut self, policy: FailurePolicy) -> Self {
+        self.failure_policy = Some(policy);
         self
     }
 
@@ -100,10 +474,207 @@ impl TelemetryConfig {
         self
     }
 
+    pub fn with_otlp_protocol(mut self, protocol: OtlpProtocol) -> Self {
+        self.otlp_protocol = protocol;
+        self
+    }
+
+    pub fn with_otlp_export(mut self, export: OtlpExportConfig) -> Self {
+        self.otlp_export = export;
+        self
+    }
+
+    pub fn with_otlp_traces_export(mut self, export: OtlpExportConfig) -> Self {
+        self.otlp_traces_export = export;
+        self
+    }
+
+    pub fn with_otlp_tls(mut self, tls: OtlpTlsConfig) -> Self {
+        self.otlp_tls = tls;
+        self
+    }
+
+    pub fn with_traces_exporter(mut self, exporter: TracesExporter) -> Self {
+        self.traces_exporter = exporter;
+        self
+    }
+
+    /// Check every setting, reporting all problems at once as
+    /// [`TelemetryError::InvalidConfig`]
+    pub fn validate(&self) -> Result<(), TelemetryError> {
+        let mut problems = Vec::new();
+
+        if self.service_name.trim().is_empty() {
+            problems.push(ConfigProblem::new("service_name", "must not be empty"));
+        }
+        if let Some(endpoint) = &self.otlp_endpoint {
+            check_endpoint("otlp_endpoint", endpoint, &mut problems);
+        }
+        if let Some(name) = &self.unregistered_backend {
+            problems.push(ConfigProblem::new(
+                "TELEMETRY_BACKEND",
+                format!("no backend is registered as {:?}", name),
+            ));
+        }
+        if let Err(e) = EnvFilter::builder().parse(&self.log_level) {
+            problems.push(ConfigProblem::new(
+                "log_level",
+                format!("invalid filter directives {:?}: {}", self.log_level, e),
+            ));
+        }
+        if let SamplerConfig::TraceIdRatio(ratio) | SamplerConfig::ParentBasedTraceIdRatio(ratio) =
+            self.sampler
+        {
+            if !(0.0..=1.0).contains(&ratio) {
+                problems.push(ConfigProblem::new(
+                    "sampler",
+                    format!("ratio {} is outside 0.0..=1.0", ratio),
+                ));
+            }
+        }
+        self.validate_tls(&mut problems);
+
+        #[cfg(feature = "telemetry-gcp")]
+        if let TelemetryBackend::Gcp(gcp_config) = &self.backend {
+            gcp_config.validate(&mut problems);
+        }
+        #[cfg(feature = "telemetry-aws")]
+        if let TelemetryBackend::Aws(aws_config) = &self.backend {
+            aws_config.validate(&mut problems);
+        }
+        #[cfg(feature = "telemetry-zipkin")]
+        if let TelemetryBackend::Zipkin(zipkin_config) = &self.backend {
+            zipkin_config.validate(&mut problems);
+        }
+        for backend in &self.secondary_backends {
+            backend.validate(self, &mut problems);
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(TelemetryError::InvalidConfig(problems))
+        }
+    }
+
+    /// TLS settings that contradict each other or the protocol
+    fn validate_tls(&self, problems: &mut Vec<ConfigProblem>) {
+        let tls = &self.otlp_tls;
+
+        if tls.client_certificate.is_some() != tls.client_key.is_some() {
+            problems.push(ConfigProblem::new(
+                "otlp_tls",
+                "client_certificate and client_key must be set together",
+            ));
+        }
+        if tls.insecure
+            && (tls.ca_certificate.is_some() || tls.client_certificate.is_some())
+        {
+            problems.push(ConfigProblem::new(
+                "otlp_tls",
+                "insecure cannot be combined with CA or client certificates",
+            ));
+        }
+        if tls.domain_name.is_some() && self.otlp_protocol != OtlpProtocol::Grpc {
+            problems.push(ConfigProblem::new(
+                "otlp_tls",
+                "domain_name is only supported with the gRPC protocol",
+            ));
+        }
+        let https_endpoint = self
+            .otlp_endpoint
+            .as_deref()
+            .is_some_and(|endpoint| endpoint.starts_with("https://"));
+        if tls.insecure && https_endpoint && self.otlp_protocol == OtlpProtocol::Grpc {
+            problems.push(ConfigProblem::new(
+                "otlp_tls",
+                "insecure gRPC sends plaintext, but otlp_endpoint uses https",
+            ));
+        }
+    }
+
+    /// Exporter settings for traces: trace-only values first, then the general ones
+    pub fn trace_export(&self) -> OtlpExportConfig {
+        self.otlp_traces_export.or(&self.otlp_export)
+    }
+
     pub fn with_log_level(mut self, level: impl Into<String>) -> Self {
         self.log_level = level.into();
         self
     }
+
+    pub fn with_export_logs(mut self, enabled: bool) -> Self {
+        self.export_logs = enabled;
+        self
+    }
+
+    pub fn with_sampler(mut self, sampler: SamplerConfig) -> Self {
+        self.sampler = sampler;
+        self
+    }
+
+    /// Add a resource attribute; overrides detected and platform attributes
+    pub fn with_resource_attribute(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.resource_attributes.push((key.into(), value.into()));
+        self
+    }
+
+    /// Replace the resource detectors (empty to disable detection)
+    pub fn with_resource_detectors(mut self, detectors: Vec<Arc<dyn ResourceDetector>>) -> Self {
+        self.resource_detectors = detectors;
+        self
+    }
+}
+
+impl Default for TelemetryConfig {
+    /// Package name and version, no OTLP endpoint, `info` pretty logs, local backend
+    fn default() -> Self {
+        Self::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+    }
+}
+
+/// The one key [`TelemetryConfig::from_file`] needs to see before defaults fill it in
+#[derive(Deserialize)]
+struct BackendKey {
+    backend: Option<serde::de::IgnoredAny>,
+}
+
+/// `name` if it is set but no backend is registered under it
+fn unregistered_backend(name: Option<&str>) -> Option<String> {
+    let name = name?.trim();
+    (!name.is_empty() && CustomBackend::named(name).is_none()).then(|| name.to_string())
+}
+
+/// Parse a variable's value, treating unknown values as unset
+fn env_parsed<T>(name: &str, parse: impl Fn(&str) -> Option<T>) -> Option<T> {
+    env::var(name).ok().and_then(|value| parse(&value))
+}
+
+fn deserialize_attributes<'de, D>(deserializer: D) -> Result<Vec<(String, String)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let attributes = BTreeMap::<String, String>::deserialize(deserializer)?;
+    Ok(attributes.into_iter().collect())
+}
+
+/// Record a problem unless `value` is an absolute `http`/`https` URL with a host
+pub(crate) fn check_endpoint(field: &'static str, value: &str, problems: &mut Vec<ConfigProblem>) {
+    let message = match url::Url::parse(value) {
+        Err(e) => format!("{:?} is not a valid URL: {}", value, e),
+        Ok(url) if !matches!(url.scheme(), "http" | "https") => {
+            format!("{:?} must use http or https, not {}", value, url.scheme())
+        }
+        Ok(url) if url.host_str().is_none_or(str::is_empty) => {
+            format!("{:?} has no host", value)
+        }
+        Ok(_) => return,
+    };
+    problems.push(ConfigProblem::new(field, message));
 }
 
 #[derive(Default)]
@@ -111,9 +682,20 @@ pub struct TelemetryConfigBuilder {
     service_name: Option<String>,
     service_version: Option<String>,
     otlp_endpoint: Option<String>,
+    otlp_protocol: Option<OtlpProtocol>,
+    otlp_export: Option<OtlpExportConfig>,
+    otlp_traces_export: Option<OtlpExportConfig>,
+    otlp_tls: Option<OtlpTlsConfig>,
+    traces_exporter: Option<TracesExporter>,
     log_level: Option<String>,
     log_format: Option<LogFormat>,
+    export_logs: Option<bool>,
+    sampler: Option<SamplerConfig>,
+    resource_attributes: Vec<(String, String)>,
+    resource_detectors: Option<Vec<Arc<dyn ResourceDetector>>>,
     backend: Option<TelemetryBackend>,
+    secondary_backends: Vec<SecondaryBackend>,
+    failure_policy: Option<FailurePolicy>,
 }
 
 impl TelemetryConfigBuilder {
@@ -132,6 +714,36 @@ impl TelemetryConfigBuilder {
         self
     }
 
+    /// OTLP wire protocol used by the default provider (gRPC by default)
+    pub fn otlp_protocol(mut self, protocol: OtlpProtocol) -> Self {
+        self.otlp_protocol = Some(protocol);
+        self
+    }
+
+    /// Headers, timeout and compression for every OTLP exporter
+    pub fn otlp_export(mut self, export: OtlpExportConfig) -> Self {
+        self.otlp_export = Some(export);
+        self
+    }
+
+    /// Trace-only exporter overrides
+    pub fn otlp_traces_export(mut self, export: OtlpExportConfig) -> Self {
+        self.otlp_traces_export = Some(export);
+        self
+    }
+
+    /// TLS / mTLS settings for OTLP exporters
+    pub fn otlp_tls(mut self, tls: OtlpTlsConfig) -> Self {
+        self.otlp_tls = Some(tls);
+        self
+    }
+
+    /// Span destination for the default provider (OTLP by default)
+    pub fn traces_exporter(mut self, exporter: TracesExporter) -> Self {
+        self.traces_exporter = Some(exporter);
+        self
+    }
+
     pub fn log_level(mut self, level: impl Into<String>) -> Self {
         self.log_level = Some(level.into());
         self
@@ -150,11 +762,52 @@ impl TelemetryConfigBuilder {
         self.log_format(LogFormat::Pretty)
     }
 
+    #[cfg(feature = "telemetry-gcp")]
+    pub fn gcp_json(self) -> Self {
+        self.log_format(LogFormat::GcpJson)
+    }
+
+    /// Export tracing events as OTLP logs
+    pub fn export_logs(mut self, enabled: bool) -> Self {
+        self.export_logs = Some(enabled);
+        self
+    }
+
+    /// Trace sampling strategy (defaults to `parentbased_always_on`)
+    pub fn sampler(mut self, sampler: SamplerConfig) -> Self {
+        self.sampler = Some(sampler);
+        self
+    }
+
+    /// Add a resource attribute
+    pub fn resource_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.resource_attributes.push((key.into(), value.into()));
+        self
+    }
+
+    /// Resource detectors (host, process, OS and container by default)
+    pub fn resource_detectors(mut self, detectors: Vec<Arc<dyn ResourceDetector>>) -> Self {
+        self.resource_detectors = Some(detectors);
+        self
+    }
+
     pub fn backend(mut self, backend: TelemetryBackend) -> Self {
         self.backend = Some(backend);
         self
     }
 
+    /// Also send spans to `backend`
+    pub fn secondary_backend(mut self, backend: SecondaryBackend) -> Self {
+        self.secondary_backends.push(backend);
+        self
+    }
+
+    /// Reaction to a backend that fails to initialize (fail fast by default)
+    pub fn failure_policy(mut self, policy: FailurePolicy) -> Self {
+        self.failure_policy = Some(policy);
+        self
+    }
+
     #[cfg(feature = "telemetry-gcp")]
     pub fn gcp(self, gcp_config: crate::telemetry::gcp::GcpConfig) -> Self {
         self.backend(TelemetryBackend::Gcp(gcp_config))
@@ -169,9 +822,22 @@ impl TelemetryConfigBuilder {
                 .service_version
                 .unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string()),
             otlp_endpoint: self.otlp_endpoint,
+            otlp_protocol: self.otlp_protocol.unwrap_or_default(),
+            otlp_export: self.otlp_export.unwrap_or_default(),
+            otlp_traces_export: self.otlp_traces_export.unwrap_or_default(),
+            otlp_tls: self.otlp_tls.unwrap_or_default(),
+            traces_exporter: self.traces_exporter.unwrap_or_default(),
             log_level: self.log_level.unwrap_or_else(|| "info".to_string()),
             log_format: self.log_format.unwrap_or_default(),
+            export_logs: self.export_logs.unwrap_or(false),
+            sampler: self.sampler.unwrap_or_default(),
+            resource_attributes: self.resource_attributes,
+            resource_detectors: self.resource_detectors.unwrap_or_else(default_detectors),
+            backend_selected: self.backend.is_some(),
             backend: self.backend.unwrap_or_default(),
+            secondary_backends: self.secondary_backends,
+            failure_policy: self.failure_policy,
+            unregistered_backend: None,
         }
     }
 }
@@ -179,12 +845,55 @@ impl TelemetryConfigBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::telemetry::testing::EnvGuard;
 
     #[test]
     fn log_format_default_is_pretty() {
         assert_eq!(LogFormat::default(), LogFormat::Pretty);
     }
 
+    #[test]
+    fn otlp_protocol_parses_spec_values() {
+        assert_eq!(OtlpProtocol::parse("grpc"), Some(OtlpProtocol::Grpc));
+        assert_eq!(
+            OtlpProtocol::parse("http/protobuf"),
+            Some(OtlpProtocol::HttpProtobuf)
+        );
+        assert_eq!(OtlpProtocol::parse("http/json"), Some(OtlpProtocol::HttpJson));
+        assert_eq!(OtlpProtocol::parse("http"), None);
+    }
+
+    #[test]
+    fn otlp_protocol_from_env_defaults_to_grpc() {
+        let _guard = EnvGuard::new(&["OTEL_EXPORTER_OTLP_PROTOCOL"]);
+        assert_eq!(OtlpProtocol::from_env(), OtlpProtocol::Grpc);
+    }
+
+    #[test]
+    fn traces_exporter_parses_spec_values() {
+        assert_eq!(TracesExporter::parse("otlp"), Some(TracesExporter::Otlp));
+        assert_eq!(
+            TracesExporter::parse("console"),
+            Some(TracesExporter::Console)
+        );
+        assert_eq!(TracesExporter::parse("none"), Some(TracesExporter::None));
+        assert_eq!(TracesExporter::parse("zipkin"), None);
+    }
+
+    #[test]
+    fn failure_policy_parses_values() {
+        assert_eq!(FailurePolicy::parse("fail_fast"), Some(FailurePolicy::FailFast));
+        assert_eq!(
+            FailurePolicy::parse("fallback_to_local"),
+            Some(FailurePolicy::FallbackToLocal)
+        );
+        assert_eq!(
+            FailurePolicy::parse(" retry_in_background "),
+            Some(FailurePolicy::RetryInBackground)
+        );
+        assert_eq!(FailurePolicy::parse("ignore"), None);
+    }
+
     #[test]
     fn telemetry_backend_default_is_local() {
         assert_eq!(TelemetryBackend::default(), TelemetryBackend::Local);
@@ -200,6 +909,10 @@ mod tests {
         assert_eq!(config.log_format, LogFormat::Pretty);
         assert_eq!(config.backend, TelemetryBackend::Local);
         assert!(config.otlp_endpoint.is_none());
+        assert_eq!(config.otlp_protocol, OtlpProtocol::Grpc);
+        assert_eq!(config.traces_exporter, TracesExporter::Otlp);
+        assert!(!config.export_logs);
+        assert_eq!(config.sampler, SamplerConfig::ParentBasedAlwaysOn);
     }
 
     #[test]
@@ -221,6 +934,7 @@ mod tests {
             .service_version("2.0.0")
             .log_level("warn")
             .otlp_endpoint("http://collector:4317")
+            .otlp_protocol(OtlpProtocol::HttpJson)
             .json()
             .build();
 
@@ -229,6 +943,48 @@ mod tests {
         assert_eq!(config.log_level, "warn");
         assert_eq!(config.log_format, LogFormat::Json);
         assert_eq!(config.otlp_endpoint, Some("http://collector:4317".to_string()));
+        assert_eq!(config.otlp_protocol, OtlpProtocol::HttpJson);
+    }
+
+    #[test]
+    fn builder_export_logs_is_independent_of_format() {
+        let config = TelemetryConfig::builder().json().export_logs(true).build();
+
+        assert!(config.export_logs);
+        assert_eq!(config.log_format, LogFormat::Json);
+    }
+
+    #[test]
+    fn builder_sets_sampler() {
+        let config = TelemetryConfig::builder()
+            .sampler(SamplerConfig::TraceIdRatio(0.5))
+            .build();
+
+        assert_eq!(config.sampler, SamplerConfig::TraceIdRatio(0.5));
+    }
+
+    #[test]
+    fn trace_export_prefers_trace_settings() {
+        use crate::telemetry::otlp::OtlpCompression;
+        use std::time::Duration;
+
+        let config = TelemetryConfig::builder()
+            .otlp_export(
+                OtlpExportConfig::default()
+                    .with_header("api-key", "general")
+                    .with_compression(OtlpCompression::Gzip),
+            )
+            .otlp_traces_export(OtlpExportConfig::default().with_timeout(Duration::from_secs(3)))
+            .build();
+
+        let traces = config.trace_export();
+
+        assert_eq!(
+            traces.headers,
+            Some(vec![("api-key".to_string(), "general".to_string())])
+        );
+        assert_eq!(traces.timeout, Some(Duration::from_secs(3)));
+        assert_eq!(traces.compression, Some(OtlpCompression::Gzip));
     }
 
     #[test]
@@ -243,6 +999,13 @@ mod tests {
         assert_eq!(config.log_format, LogFormat::Json);
     }
 
+    #[cfg(feature = "telemetry-gcp")]
+    #[test]
+    fn builder_gcp_json_sets_log_format() {
+        let config = TelemetryConfig::builder().gcp_json().build();
+        assert_eq!(config.log_format, LogFormat::GcpJson);
+    }
+
     #[test]
     fn builder_uses_defaults_when_not_set() {
         let config = TelemetryConfig::builder().build();
@@ -255,9 +1018,7 @@ mod tests {
     #[test]
     fn backend_from_env_returns_local_without_gcp_project() {
         // Ensure no GCP project env var is set
-        std::env::remove_var("GOOGLE_CLOUD_PROJECT");
-        std::env::remove_var("GCLOUD_PROJECT");
-        std::env::remove_var("GCP_PROJECT");
+        let _guard = EnvGuard::new(&["GOOGLE_CLOUD_PROJECT", "GCLOUD_PROJECT", "GCP_PROJECT"]);
 
         let backend = TelemetryBackend::from_env();
 
@@ -267,18 +1028,18 @@ mod tests {
     #[cfg(feature = "telemetry-gcp")]
     #[test]
     fn backend_from_env_returns_gcp_with_project() {
+        let _guard = EnvGuard::new(&["GOOGLE_CLOUD_PROJECT"]);
         std::env::set_var("GOOGLE_CLOUD_PROJECT", "test-project");
 
         let backend = TelemetryBackend::from_env();
 
         assert!(matches!(backend, TelemetryBackend::Gcp(_)));
-
-        std::env::remove_var("GOOGLE_CLOUD_PROJECT");
     }
 
     #[cfg(feature = "telemetry-gcp")]
     #[test]
     fn config_from_env_auto_detects_gcp() {
+        let _guard = EnvGuard::new(&["GOOGLE_CLOUD_PROJECT", "LOG_FORMAT"]);
         std::env::set_var("GOOGLE_CLOUD_PROJECT", "auto-detect-project");
         std::env::set_var("LOG_FORMAT", "json");
 
@@ -286,8 +1047,363 @@ mod tests {
 
         assert!(matches!(config.backend, TelemetryBackend::Gcp(_)));
         assert_eq!(config.log_format, LogFormat::Json);
+    }
+
+    fn problem_fields(config: &TelemetryConfig) -> Vec<&'static str> {
+        match config.validate() {
+            Ok(()) => Vec::new(),
+            Err(TelemetryError::InvalidConfig(problems)) => {
+                problems.iter().map(|problem| problem.field).collect()
+            }
+            Err(e) => panic!("unexpected error: {}", e),
+        }
+    }
+
+    #[test]
+    fn validate_accepts_defaults() {
+        let config = TelemetryConfig::new("svc", "1.0").with_otlp_endpoint("http://localhost:4317");
+
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_collects_every_problem() {
+        let config = TelemetryConfig::new(" ", "1.0")
+            .with_otlp_endpoint("invalid-url")
+            .with_log_level("info,my_crate=loud")
+            .with_sampler(SamplerConfig::TraceIdRatio(1.5));
+
+        assert_eq!(
+            problem_fields(&config),
+            vec!["service_name", "otlp_endpoint", "log_level", "sampler"]
+        );
+    }
+
+    #[test]
+    fn validate_rejects_unregistered_env_backend() {
+        crate::telemetry::registry::register_backend(
+            "config-env-test",
+            crate::telemetry::default::DefaultProvider,
+        );
+        assert_eq!(unregistered_backend(Some("config-env-test")), None);
+        assert_eq!(unregistered_backend(None), None);
+
+        let mut config = TelemetryConfig::new("svc", "1.0");
+        config.unregistered_backend = unregistered_backend(Some(" acme-typo "));
+
+        match config.validate() {
+            Err(TelemetryError::InvalidConfig(problems)) => {
+                assert_eq!(problems.len(), 1);
+                assert_eq!(problems[0].field, "TELEMETRY_BACKEND");
+                assert!(problems[0].message.contains("acme-typo"));
+            }
+            other => panic!("expected InvalidConfig, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn validate_rejects_bad_endpoints() {
+        for endpoint in ["invalid-url", "ftp://collector:21", "http://", "localhost:4317"] {
+            let config = TelemetryConfig::new("svc", "1.0").with_otlp_endpoint(endpoint);
+            assert_eq!(problem_fields(&config), vec!["otlp_endpoint"], "{}", endpoint);
+        }
+    }
+
+    #[test]
+    fn validate_rejects_incompatible_tls_options() {
+        let config = TelemetryConfig::new("svc", "1.0")
+            .with_otlp_endpoint("https://collector:4318")
+            .with_otlp_protocol(OtlpProtocol::HttpProtobuf)
+            .with_otlp_tls(
+                OtlpTlsConfig {
+                    client_certificate: Some("client.pem".into()),
+                    ..OtlpTlsConfig::default()
+                }
+                .with_insecure(true)
+                .with_domain_name("collector.internal"),
+            );
+
+        assert_eq!(
+            problem_fields(&config),
+            vec!["otlp_tls", "otlp_tls", "otlp_tls"]
+        );
+
+        let insecure_https = TelemetryConfig::new("svc", "1.0")
+            .with_otlp_endpoint("https://collector:4317")
+            .with_otlp_tls(OtlpTlsConfig::default().with_insecure(true));
+        assert_eq!(problem_fields(&insecure_https), vec!["otlp_tls"]);
+    }
+
+    #[test]
+    fn invalid_config_error_lists_problems() {
+        let err = TelemetryConfig::new("", "1.0")
+            .with_otlp_endpoint("invalid-url")
+            .validate()
+            .unwrap_err();
+
+        let message = err.to_string();
+        assert!(message.starts_with("Invalid configuration: service_name: must not be empty; "));
+        assert!(message.contains("otlp_endpoint: \"invalid-url\" is not a valid URL"));
+    }
+
+    /// Write `contents` to a temp file named `name`
+    fn config_file(name: &str, contents: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(name);
+        std::fs::write(&path, contents).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn from_file_reads_toml() {
+        let (_dir, path) = config_file(
+            "telemetry.toml",
+            r#"
+            service_name = "from-file"
+            otlp_endpoint = "http://collector:4318"
+            otlp_protocol = "http/protobuf"
+            traces_exporter = "console"
+            log_level = "debug"
+            log_format = "json"
+            export_logs = true
+            sampler = { name = "parentbased_traceidratio", ratio = 0.25 }
+
+            [otlp_export]
+            timeout_ms = 2500
+            compression = "gzip"
+            headers = { x-api-key = "secret" }
+
+            [otlp_tls]
+            ca_certificate = "/etc/ca.pem"
+
+            [resource_attributes]
+            "deployment.environment.name" = "prod"
+            "#,
+        );
+
+        let config = TelemetryConfig::from_file(&path).unwrap();
+
+        assert_eq!(config.service_name, "from-file");
+        assert_eq!(config.service_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(config.otlp_endpoint.as_deref(), Some("http://collector:4318"));
+        assert_eq!(config.otlp_protocol, OtlpProtocol::HttpProtobuf);
+        assert_eq!(config.traces_exporter, TracesExporter::Console);
+        assert_eq!(config.log_level, "debug");
+        assert_eq!(config.log_format, LogFormat::Json);
+        assert!(config.export_logs);
+        assert_eq!(config.sampler, SamplerConfig::ParentBasedTraceIdRatio(0.25));
+        assert_eq!(
+            config.otlp_export,
+            OtlpExportConfig::default()
+                .with_header("x-api-key", "secret")
+                .with_timeout(std::time::Duration::from_millis(2500))
+                .with_compression(crate::telemetry::otlp::OtlpCompression::Gzip)
+        );
+        assert_eq!(
+            config.otlp_tls,
+            OtlpTlsConfig::default().with_ca_certificate("/etc/ca.pem")
+        );
+        assert_eq!(
+            config.resource_attributes,
+            vec![("deployment.environment.name".to_string(), "prod".to_string())]
+        );
+        assert_eq!(config.backend, TelemetryBackend::Local);
+        assert!(!config.resource_detectors.is_empty());
+    }
+
+    #[test]
+    fn from_file_trace_compression_none_overrides_general_gzip() {
+        use crate::telemetry::otlp::OtlpCompression;
+
+        let (_dir, path) = config_file(
+            "telemetry.toml",
+            "[otlp_export]
+             compression = \"gzip\"
+
+             [otlp_traces_export]
+             compression = \"none\"
+",
+        );
+
+        let config = TelemetryConfig::from_file(&path).unwrap();
+
+        assert_eq!(config.otlp_export.compression, Some(OtlpCompression::Gzip));
+        assert_eq!(config.trace_export().compression, Some(OtlpCompression::None));
+    }
+
+    #[cfg(all(feature = "telemetry-gcp", feature = "telemetry-yaml"))]
+    #[test]
+    fn from_file_reads_yaml_gcp_backend() {
+        use crate::telemetry::gcp::{GcpConfig, GcpPlatform};
+
+        let (_dir, path) = config_file(
+            "telemetry.yaml",
+            "service_name: from-yaml\n\
+             sampler: always_off\n\
+             backend:\n  \
+               gcp:\n    \
+                 project_id: my-project\n    \
+                 platform: kubernetes_engine\n",
+        );
+
+        let config = TelemetryConfig::from_file(&path).unwrap();
+
+        assert_eq!(config.service_name, "from-yaml");
+        assert_eq!(config.sampler, SamplerConfig::AlwaysOff);
+        assert_eq!(
+            config.backend,
+            TelemetryBackend::Gcp(
+                GcpConfig::new("my-project").with_platform(GcpPlatform::KubernetesEngine)
+            )
+        );
+    }
+
+    #[cfg(feature = "telemetry-aws")]
+    #[test]
+    fn from_file_reads_toml_aws_backend() {
+        use crate::telemetry::aws::{AwsConfig, AwsPlatform};
+
+        let (_dir, path) = config_file(
+            "telemetry.toml",
+            "[backend.aws]
+             region = \"eu-west-1\"
+             platform = \"eks\"
+",
+        );
+
+        let config = TelemetryConfig::from_file(&path).unwrap();
+
+        assert_eq!(
+            config.backend,
+            TelemetryBackend::Aws(
+                AwsConfig::new()
+                    .with_region("eu-west-1")
+                    .with_platform(AwsPlatform::Eks)
+            )
+        );
+    }
+
+    #[cfg(feature = "telemetry-zipkin")]
+    #[test]
+    fn from_file_reads_toml_zipkin_backend() {
+        use crate::telemetry::zipkin::ZipkinConfig;
+
+        let (_dir, path) = config_file(
+            "telemetry.toml",
+            "[backend.zipkin]
+             endpoint = \"http://zipkin.internal:9411/api/v2/spans\"
+",
+        );
+
+        let config = TelemetryConfig::from_file(&path).unwrap();
+
+        assert_eq!(
+            config.backend,
+            TelemetryBackend::Zipkin(ZipkinConfig::new(
+                "http://zipkin.internal:9411/api/v2/spans"
+            ))
+        );
+    }
+
+    #[test]
+    fn from_file_marks_named_backend_as_selected() {
+        let (_dir, local) = config_file("telemetry.toml", "backend = \"local\"\n");
+        let (_dir2, unset) = config_file("telemetry.toml", "service_name = \"svc\"\n");
+
+        let local = TelemetryConfig::from_file(&local).unwrap();
+        let unset = TelemetryConfig::from_file(&unset).unwrap();
+
+        assert_eq!(local.backend, TelemetryBackend::Local);
+        assert!(local.backend_selected());
+        assert!(!unset.backend_selected());
+        assert!(TelemetryConfig::builder()
+            .backend(TelemetryBackend::Local)
+            .build()
+            .backend_selected());
+        assert!(!TelemetryConfig::builder().build().backend_selected());
+    }
+
+    #[test]
+    fn from_file_rejects_unknown_keys_and_formats() {
+        let (_dir, typo) = config_file("telemetry.toml", "servce_name = \"oops\"\n");
+        let (_dir2, ini) = config_file("telemetry.ini", "service_name = x\n");
+
+        assert!(matches!(
+            TelemetryConfig::from_file(&typo),
+            Err(TelemetryError::Config(msg)) if msg.contains("servce_name")
+        ));
+        assert!(matches!(
+            TelemetryConfig::from_file(&ini),
+            Err(TelemetryError::Config(_))
+        ));
+        assert!(matches!(
+            TelemetryConfig::from_file("/nonexistent/telemetry.toml"),
+            Err(TelemetryError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn load_layers_env_over_file() {
+        let (_dir, path) = config_file(
+            "telemetry.toml",
+            r#"
+            service_version = "1.0.0-file"
+            traces_exporter = "none"
+
+            [otlp_export]
+            timeout_ms = 2500
+            headers = { x-api-key = "secret" }
+            "#,
+        );
+        let _guard = EnvGuard::new(&[
+            "TELEMETRY_CONFIG_FILE",
+            "OTEL_SERVICE_VERSION",
+            "OTEL_EXPORTER_OTLP_TIMEOUT",
+        ]);
+        std::env::set_var("TELEMETRY_CONFIG_FILE", &path);
+        std::env::set_var("OTEL_SERVICE_VERSION", "2.0.0-env");
+        std::env::set_var("OTEL_EXPORTER_OTLP_TIMEOUT", "500");
+
+        let config = TelemetryConfig::load().unwrap();
+
+        // Set variables win over the file...
+        assert_eq!(config.service_version, "2.0.0-env");
+        assert_eq!(
+            config.otlp_export.timeout,
+            Some(std::time::Duration::from_millis(500))
+        );
+        // ...unset ones leave file values alone, down to individual fields
+        assert_eq!(config.traces_exporter, TracesExporter::None);
+        assert_eq!(
+            config.otlp_export.headers,
+            Some(vec![("x-api-key".to_string(), "secret".to_string())])
+        );
+    }
+
+    #[test]
+    fn code_settings_override_loaded_config() {
+        let (_dir, path) = config_file("telemetry.toml", "log_level = \"warn\"\n");
+
+        let config = TelemetryConfig::from_file(&path)
+            .unwrap()
+            .with_log_level("trace");
+
+        assert_eq!(config.log_level, "trace");
+    }
+
+    #[test]
+    fn config_from_env_reads_resource_attributes() {
+        let _guard = EnvGuard::new(&["OTEL_RESOURCE_ATTRIBUTES"]);
+        std::env::set_var(
+            "OTEL_RESOURCE_ATTRIBUTES",
+            "service.name=from-attrs,deployment.environment.name=staging%201",
+        );
+
+        let config = TelemetryConfig::from_env();
 
-        std::env::remove_var("GOOGLE_CLOUD_PROJECT");
-        std::env::remove_var("LOG_FORMAT");
+        assert_eq!(config.service_name, "from-attrs");
+        assert!(config
+            .resource_attributes
+            .contains(&("deployment.environment.name".to_string(), "staging 1".to_string())));
     }
 }
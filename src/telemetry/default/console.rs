@@ -0,0 +1,199 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use opentelemetry::trace::{SpanId, Status};
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
+use opentelemetry_sdk::trace::{SpanData, SpanExporter};
+
+/// Span exporter printing finished spans in a readable form.
+///
+/// Each span shows its name, kind, duration and status, its trace, span and
+/// parent IDs, then its attributes and events:
+///
+/// ```text
+/// span "GET /users" server 12.345ms status=unset
+///   trace=4bf92f3577b34da6a3ce929d0e0e4736 span=00f067aa0ba902b7 parent=root
+///   http.method = GET
+///   event "cache miss" +1.200ms
+///     key = user:1
+/// ```
+pub struct ConsoleSpanExporter {
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+impl ConsoleSpanExporter {
+    /// Print to stdout
+    pub fn stdout() -> Self {
+        Self::with_writer(io::stdout())
+    }
+
+    /// Print to any writer
+    pub fn with_writer(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Mutex::new(Box::new(writer)),
+        }
+    }
+}
+
+impl Default for ConsoleSpanExporter {
+    fn default() -> Self {
+        Self::stdout()
+    }
+}
+
+impl fmt::Debug for ConsoleSpanExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConsoleSpanExporter")
+            .finish_non_exhaustive()
+    }
+}
+
+impl SpanExporter for ConsoleSpanExporter {
+    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|e| OTelSdkError::InternalFailure(e.to_string()))?;
+
+        for span in &batch {
+            write_span(&mut *writer, span)
+                .map_err(|e| OTelSdkError::InternalFailure(e.to_string()))?;
+        }
+        writer
+            .flush()
+            .map_err(|e| OTelSdkError::InternalFailure(e.to_string()))
+    }
+}
+
+fn write_span(out: &mut dyn Write, span: &SpanData) -> io::Result<()> {
+    let kind = format!("{:?}", span.span_kind).to_lowercase();
+    writeln!(
+        out,
+        "span \"{}\" {} {} status={}",
+        span.name,
+        kind,
+        format_duration(elapsed(span.start_time, span.end_time)),
+        format_status(&span.status),
+    )?;
+
+    let context = &span.span_context;
+    let parent = if span.parent_span_id == SpanId::INVALID {
+        "root".to_string()
+    } else if span.parent_span_is_remote {
+        format!("{} (remote)", span.parent_span_id)
+    } else {
+        span.parent_span_id.to_string()
+    };
+    writeln!(
+        out,
+        "  trace={} span={} parent={}",
+        context.trace_id(),
+        context.span_id(),
+        parent
+    )?;
+
+    write_attributes(out, "  ", &span.attributes)?;
+    for event in span.events.iter() {
+        writeln!(
+            out,
+            "  event \"{}\" +{}",
+            event.name,
+            format_duration(elapsed(span.start_time, event.timestamp))
+        )?;
+        write_attributes(out, "    ", &event.attributes)?;
+    }
+
+    Ok(())
+}
+
+fn write_attributes(out: &mut dyn Write, indent: &str, attributes: &[KeyValue]) -> io::Result<()> {
+    for attribute in attributes {
+        writeln!(out, "{}{} = {}", indent, attribute.key, attribute.value)?;
+    }
+    Ok(())
+}
+
+fn format_status(status: &Status) -> String {
+    match status {
+        Status::Unset => "unset".to_string(),
+        Status::Ok => "ok".to_string(),
+        Status::Error { description } => format!("error({})", description),
+    }
+}
+
+/// Time between two instants, zero if the clock went backwards
+fn elapsed(start: SystemTime, end: SystemTime) -> Duration {
+    end.duration_since(start).unwrap_or_default()
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{Span, TraceContextExt, Tracer, TracerProvider};
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use std::sync::Arc;
+
+    /// Writer sharing its buffer with the test
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Buffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    #[test]
+    fn prints_span_tree_with_attributes_and_events() {
+        let buffer = Buffer::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(ConsoleSpanExporter::with_writer(buffer.clone()))
+            .build();
+        let tracer = provider.tracer("test");
+
+        let (parent_id, child_id) = tracer.in_span("parent", |cx| {
+            let parent_id = cx.span().span_context().span_id();
+            let mut child = tracer.start("child");
+            child.set_attribute(KeyValue::new("http.method", "GET"));
+            child.add_event("cache miss", vec![KeyValue::new("key", "user:1")]);
+            let child_id = child.span_context().span_id();
+            child.end();
+            (parent_id, child_id)
+        });
+
+        let output = buffer.contents();
+
+        assert!(output.contains("span \"child\" internal "));
+        assert!(output.contains(&format!("span={} parent={}", child_id, parent_id)));
+        assert!(output.contains(&format!("span={} parent=root", parent_id)));
+        assert!(output.contains("  http.method = GET\n"));
+        assert!(output.contains("  event \"cache miss\" +"));
+        assert!(output.contains("    key = user:1\n"));
+        // Children end, and so print, before their parent
+        assert!(output.find("\"child\"") < output.find("\"parent\""));
+    }
+
+    #[test]
+    fn formats_status_and_duration() {
+        assert_eq!(format_status(&Status::Unset), "unset");
+        assert_eq!(format_status(&Status::error("boom")), "error(boom)");
+        assert_eq!(format_duration(Duration::from_micros(12_345)), "12.345ms");
+    }
+}
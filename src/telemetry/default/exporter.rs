@@ -0,0 +1,174 @@
+use opentelemetry_otlp::{
+    LogExporter, MetricExporter, Protocol, SpanExporter, WithExportConfig, WithHttpConfig,
+    WithTonicConfig,
+};
+
+use crate::telemetry::config::{OtlpProtocol, TelemetryConfig};
+use crate::telemetry::error::TelemetryError;
+use crate::telemetry::otlp::{OtlpExportConfig, OtlpTlsConfig};
+
+/// OTLP/HTTP paths appended to the base endpoint, per signal
+const TRACES_PATH: &str = "/v1/traces";
+const METRICS_PATH: &str = "/v1/metrics";
+const LOGS_PATH: &str = "/v1/logs";
+
+pub(crate) fn build_span_exporter(
+    endpoint: &str,
+    config: &TelemetryConfig,
+) -> Result<SpanExporter, TelemetryError> {
+    let export = config.trace_export();
+    let result = match http_protocol(config.otlp_protocol) {
+        None => configure_tonic(
+            SpanExporter::builder().with_tonic().with_endpoint(endpoint),
+            &export,
+            &config.otlp_tls,
+        )?
+        .build(),
+        Some(protocol) => configure_http(
+            SpanExporter::builder()
+                .with_http()
+                .with_protocol(protocol)
+                .with_endpoint(signal_endpoint(endpoint, TRACES_PATH)),
+            &export,
+            &config.otlp_tls,
+        )?
+        .build(),
+    };
+
+    result.map_err(|e| TelemetryError::Exporter(e.to_string()))
+}
+
+pub(crate) fn build_metric_exporter(
+    endpoint: &str,
+    config: &TelemetryConfig,
+) -> Result<MetricExporter, TelemetryError> {
+    let export = &config.otlp_export;
+    let result = match http_protocol(config.otlp_protocol) {
+        None => configure_tonic(
+            MetricExporter::builder()
+                .with_tonic()
+                .with_endpoint(endpoint),
+            export,
+            &config.otlp_tls,
+        )?
+        .build(),
+        Some(protocol) => configure_http(
+            MetricExporter::builder()
+                .with_http()
+                .with_protocol(protocol)
+                .with_endpoint(signal_endpoint(endpoint, METRICS_PATH)),
+            export,
+            &config.otlp_tls,
+        )?
+        .build(),
+    };
+
+    result.map_err(|e| TelemetryError::Exporter(e.to_string()))
+}
+
+pub(crate) fn build_log_exporter(
+    endpoint: &str,
+    config: &TelemetryConfig,
+) -> Result<LogExporter, TelemetryError> {
+    let export = &config.otlp_export;
+    let result = match http_protocol(config.otlp_protocol) {
+        None => configure_tonic(
+            LogExporter::builder().with_tonic().with_endpoint(endpoint),
+            export,
+            &config.otlp_tls,
+        )?
+        .build(),
+        Some(protocol) => configure_http(
+            LogExporter::builder()
+                .with_http()
+                .with_protocol(protocol)
+                .with_endpoint(signal_endpoint(endpoint, LOGS_PATH)),
+            export,
+            &config.otlp_tls,
+        )?
+        .build(),
+    };
+
+    result.map_err(|e| TelemetryError::Exporter(e.to_string()))
+}
+
+/// Apply export settings and, when configured, TLS to a gRPC builder
+fn configure_tonic<B>(
+    builder: B,
+    export: &OtlpExportConfig,
+    tls: &OtlpTlsConfig,
+) -> Result<B, TelemetryError>
+where
+    B: WithExportConfig + WithTonicConfig,
+{
+    let mut builder = export.apply_tonic(builder);
+    if tls.is_configured() {
+        if let Some(tls_config) = tls.tonic_config()? {
+            builder = builder.with_tls_config(tls_config);
+        }
+    }
+    Ok(builder)
+}
+
+/// Apply export settings and, when configured, a TLS-aware client to an HTTP builder
+fn configure_http<B>(
+    builder: B,
+    export: &OtlpExportConfig,
+    tls: &OtlpTlsConfig,
+) -> Result<B, TelemetryError>
+where
+    B: WithExportConfig + WithHttpConfig,
+{
+    let mut builder = export.apply_http(builder);
+    if tls.is_configured() {
+        builder = builder.with_http_client(tls.http_client(export.timeout)?);
+    }
+    Ok(builder)
+}
+
+/// The OTLP/HTTP wire format, or `None` for gRPC
+fn http_protocol(protocol: OtlpProtocol) -> Option<Protocol> {
+    match protocol {
+        OtlpProtocol::Grpc => None,
+        OtlpProtocol::HttpProtobuf => Some(Protocol::HttpBinary),
+        OtlpProtocol::HttpJson => Some(Protocol::HttpJson),
+    }
+}
+
+/// Append the signal path to a base endpoint.
+///
+/// `OTEL_EXPORTER_OTLP_ENDPOINT` is a base URL for OTLP/HTTP, but the exporter
+/// uses an explicitly configured endpoint verbatim.
+fn signal_endpoint(endpoint: &str, path: &str) -> String {
+    format!("{}{}", endpoint.trim_end_matches('/'), path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signal_endpoint_appends_path() {
+        assert_eq!(
+            signal_endpoint("http://localhost:4318", TRACES_PATH),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            signal_endpoint("http://localhost:4318/", LOGS_PATH),
+            "http://localhost:4318/v1/logs"
+        );
+    }
+
+    #[test]
+    fn grpc_has_no_http_protocol() {
+        assert_eq!(http_protocol(OtlpProtocol::Grpc), None);
+        assert_eq!(
+            http_protocol(OtlpProtocol::HttpProtobuf),
+            Some(Protocol::HttpBinary)
+        );
+        assert_eq!(
+            http_protocol(OtlpProtocol::HttpJson),
+            Some(Protocol::HttpJson)
+        );
+    }
+}
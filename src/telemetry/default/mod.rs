@@ -1,12 +1,19 @@
 //! Default/local telemetry provider.
 //!
 //! This module provides a simple telemetry provider for local development
-//! that optionally exports traces to a local OTLP collector.
+//! that optionally exports traces, metrics and logs to a local OTLP collector.
 //!
 //! # Behavior
 //!
-//! - If `OTEL_EXPORTER_OTLP_ENDPOINT` is set: exports to that endpoint
+//! - If `OTEL_EXPORTER_OTLP_ENDPOINT` is set: exports traces and metrics to that endpoint,
+//!   plus logs when `OTEL_LOGS_EXPORTER=otlp`
 //! - Otherwise: no-op (traces are not exported)
+//! - `OTEL_TRACES_EXPORTER=console` prints finished spans to stdout (name,
+//!   duration, status, IDs, attributes and events) without a collector;
+//!   `none` disables trace export even when an endpoint is set
+//! - `OTEL_EXPORTER_OTLP_PROTOCOL` selects gRPC (default), `http/protobuf` or
+//!   `http/json`; for OTLP/HTTP the endpoint is a base URL and `/v1/traces`,
+//!   `/v1/metrics` and `/v1/logs` are appended per signal
 //!
 //! # Example
 //!
@@ -19,6 +26,9 @@
 //! init_with_provider(&provider, &config).await?;
 //! ```
 
+mod console;
+pub(crate) mod exporter;
 mod provider;
 
+pub use console::ConsoleSpanExporter;
 pub use provider::DefaultProvider;
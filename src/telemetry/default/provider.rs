@@ -1,41 +1,111 @@
-use opentelemetry_otlp::WithExportConfig;
-use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::logs::SdkLoggerProvider;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::trace::{SdkTracerProvider, TracerProviderBuilder};
 
 use crate::telemetry::api::TelemetryProvider;
-use crate::telemetry::config::TelemetryConfig;
+use crate::telemetry::config::{TelemetryConfig, TracesExporter};
+use crate::telemetry::default::console::ConsoleSpanExporter;
+use crate::telemetry::default::exporter;
 use crate::telemetry::error::TelemetryError;
+use crate::telemetry::fanout::with_secondary_backends;
+use crate::telemetry::logs::build_batch_logger_provider;
+use crate::telemetry::metrics::build_periodic_meter_provider;
 use crate::telemetry::resource::build_base_resource;
 
 /// Default provider for local development
-/// - Exports to local OTLP collector if configured
+/// - Exports traces, metrics and (if enabled) logs to local OTLP collector if configured
 /// - Falls back to no-op if no endpoint
+/// - `OTEL_TRACES_EXPORTER=console` prints spans to stdout instead; `none` drops them
 pub struct DefaultProvider;
 
+impl DefaultProvider {
+    /// Tracer provider builder with the configured exporter, before secondary backends
+    pub(crate) fn tracer_provider_builder(
+        config: &TelemetryConfig,
+    ) -> Result<TracerProviderBuilder, TelemetryError> {
+        Self::tracer_provider_builder_with_console(config, ConsoleSpanExporter::stdout())
+    }
+
+    /// As [`tracer_provider_builder`](Self::tracer_provider_builder), printing
+    /// to `console` when `traces_exporter` is `console`
+    fn tracer_provider_builder_with_console(
+        config: &TelemetryConfig,
+        console: ConsoleSpanExporter,
+    ) -> Result<TracerProviderBuilder, TelemetryError> {
+        let resource = build_base_resource(config);
+
+        let builder = SdkTracerProvider::builder()
+            .with_sampler(config.sampler.to_sampler())
+            .with_resource(resource);
+
+        let builder = match (config.traces_exporter, &config.otlp_endpoint) {
+            (TracesExporter::Otlp, Some(endpoint)) => {
+                let exporter = exporter::build_span_exporter(endpoint, config)?;
+
+                builder.with_batch_exporter(exporter)
+            }
+            (TracesExporter::Console, _) => {
+                // Print each span as it ends, so output follows the request flow
+                builder.with_simple_exporter(console)
+            }
+            (TracesExporter::Otlp, None) | (TracesExporter::None, _) => {
+                // No-op provider for local dev without collector
+                builder
+            }
+        };
+
+        Ok(builder)
+    }
+}
+
 impl TelemetryProvider for DefaultProvider {
     async fn build_tracer_provider(
         &self,
         config: &TelemetryConfig,
     ) -> Result<SdkTracerProvider, TelemetryError> {
+        let builder = Self::tracer_provider_builder(config)?;
+
+        Ok(with_secondary_backends(builder, config).await.build())
+    }
+
+    async fn build_meter_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkMeterProvider, TelemetryError> {
         let resource = build_base_resource(config);
 
         let provider = match &config.otlp_endpoint {
             Some(endpoint) => {
-                let exporter = opentelemetry_otlp::SpanExporter::builder()
-                    .with_tonic()
-                    .with_endpoint(endpoint)
-                    .build()
-                    .map_err(|e: opentelemetry_otlp::ExporterBuildError| {
-                        TelemetryError::Exporter(e.to_string())
-                    })?;
+                let exporter = exporter::build_metric_exporter(endpoint, config)?;
 
-                SdkTracerProvider::builder()
-                    .with_batch_exporter(exporter)
+                build_periodic_meter_provider(exporter, resource)
+            }
+            None => {
+                // No reader: instruments record into a no-op pipeline
+                SdkMeterProvider::builder()
                     .with_resource(resource)
                     .build()
             }
+        };
+
+        Ok(provider)
+    }
+
+    async fn build_logger_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkLoggerProvider, TelemetryError> {
+        let resource = build_base_resource(config);
+
+        let provider = match &config.otlp_endpoint {
+            Some(endpoint) => {
+                let exporter = exporter::build_log_exporter(endpoint, config)?;
+
+                build_batch_logger_provider(exporter, resource)
+            }
             None => {
-                // No-op provider for local dev without collector
-                SdkTracerProvider::builder()
+                // No processor: records are dropped
+                SdkLoggerProvider::builder()
                     .with_resource(resource)
                     .build()
             }
@@ -48,6 +118,14 @@ impl TelemetryProvider for DefaultProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::telemetry::config::OtlpProtocol;
+    use crate::telemetry::otlp::OtlpTlsConfig;
+    use opentelemetry_proto::tonic::collector::trace::v1::trace_service_server::{
+        TraceService, TraceServiceServer,
+    };
+    use opentelemetry_proto::tonic::collector::trace::v1::{
+        ExportTraceServiceRequest, ExportTraceServiceResponse,
+    };
 
     #[tokio::test]
     async fn default_provider_without_endpoint_succeeds() {
@@ -70,9 +148,368 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn default_provider_none_exporter_ignores_endpoint() {
+        let server = otlp_http_stub("/v1/traces").await;
+        let config = TelemetryConfig::new("test-service", "1.0.0")
+            .with_otlp_endpoint(server.uri())
+            .with_otlp_protocol(OtlpProtocol::HttpProtobuf)
+            .with_traces_exporter(TracesExporter::None);
+
+        export_span(DefaultProvider.build_tracer_provider(&config).await.unwrap()).await;
+
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn default_provider_console_exporter_prints_spans() {
+        use opentelemetry::trace::{Tracer, TracerProvider};
+
+        let output = tempfile::NamedTempFile::new().unwrap();
+        let config = TelemetryConfig::new("test-service", "1.0.0")
+            .with_traces_exporter(TracesExporter::Console);
+
+        let console = ConsoleSpanExporter::with_writer(output.reopen().unwrap());
+        let provider = DefaultProvider::tracer_provider_builder_with_console(&config, console)
+            .unwrap()
+            .build();
+        provider.tracer("test").in_span("work", |_| {});
+
+        let printed = std::fs::read_to_string(output.path()).unwrap();
+        assert!(printed.starts_with("span \"work\""), "{}", printed);
+    }
+
+    #[tokio::test]
+    async fn default_provider_applies_sampler() {
+        use crate::telemetry::sampling::SamplerConfig;
+        use opentelemetry::trace::{Span, Tracer, TracerProvider};
+
+        let config = TelemetryConfig::new("test-service", "1.0.0")
+            .with_sampler(SamplerConfig::AlwaysOff);
+
+        let provider = DefaultProvider.build_tracer_provider(&config).await.unwrap();
+        let span = provider.tracer("test").start("work");
+
+        assert!(!span.span_context().is_sampled());
+    }
+
+    #[tokio::test]
+    async fn default_provider_meter_without_endpoint_succeeds() {
+        let provider = DefaultProvider;
+        let config = TelemetryConfig::new("test-service", "1.0.0");
+
+        let result = provider.build_meter_provider(&config).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn default_provider_meter_with_otlp_endpoint_succeeds() {
+        let provider = DefaultProvider;
+        let config = TelemetryConfig::new("test-service", "1.0.0")
+            .with_otlp_endpoint("http://localhost:4317");
+
+        let result = provider.build_meter_provider(&config).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn default_provider_logger_with_otlp_endpoint_succeeds() {
+        let provider = DefaultProvider;
+        let config = TelemetryConfig::new("test-service", "1.0.0")
+            .with_otlp_endpoint("http://localhost:4317")
+            .with_export_logs(true);
+
+        let result = provider.build_logger_provider(&config).await;
+
+        assert!(result.is_ok());
+    }
+
+    /// Emit one span through the provider and flush it to the exporter
+    async fn export_span(provider: SdkTracerProvider) {
+        use opentelemetry::trace::{Tracer, TracerProvider};
+
+        provider.tracer("test").in_span("http-export", |_| {});
+        // The HTTP exporter blocks, so flush off the runtime's worker threads
+        tokio::task::spawn_blocking(move || provider.force_flush())
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    async fn otlp_http_stub(path: &str) -> wiremock::MockServer {
+        use wiremock::matchers::{method, path as request_path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(request_path(path))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn default_provider_exports_spans_over_http_protobuf() {
+        let server = otlp_http_stub("/v1/traces").await;
+        let config = TelemetryConfig::new("test-service", "1.0.0")
+            .with_otlp_endpoint(server.uri())
+            .with_otlp_protocol(OtlpProtocol::HttpProtobuf);
+
+        export_span(DefaultProvider.build_tracer_provider(&config).await.unwrap()).await;
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0].headers.get("content-type").unwrap(),
+            "application/x-protobuf"
+        );
+        assert!(!requests[0].body.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn default_provider_fans_out_to_secondary_backends() {
+        use crate::telemetry::fanout::SecondaryBackend;
+
+        let primary = otlp_http_stub("/v1/traces").await;
+        let secondary = otlp_http_stub("/v1/traces").await;
+        let config = TelemetryConfig::new("test-service", "1.0.0")
+            .with_otlp_endpoint(primary.uri())
+            .with_otlp_protocol(OtlpProtocol::HttpProtobuf)
+            .with_secondary_backend(
+                SecondaryBackend::otlp(secondary.uri()).with_otlp_protocol(OtlpProtocol::HttpJson),
+            );
+
+        export_span(DefaultProvider.build_tracer_provider(&config).await.unwrap()).await;
+
+        let primary_requests = primary.received_requests().await.unwrap();
+        let secondary_requests = secondary.received_requests().await.unwrap();
+        assert_eq!(primary_requests.len(), 1);
+        assert_eq!(secondary_requests.len(), 1);
+        assert_eq!(
+            secondary_requests[0].headers.get("content-type").unwrap(),
+            "application/json"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn default_provider_exports_spans_over_http_json() {
+        let server = otlp_http_stub("/v1/traces").await;
+        let config = TelemetryConfig::new("test-service", "1.0.0")
+            .with_otlp_endpoint(format!("{}/", server.uri()))
+            .with_otlp_protocol(OtlpProtocol::HttpJson);
+
+        export_span(DefaultProvider.build_tracer_provider(&config).await.unwrap()).await;
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0].headers.get("content-type").unwrap(),
+            "application/json"
+        );
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        let span = &body["resourceSpans"][0]["scopeSpans"][0]["spans"][0];
+        assert_eq!(span["name"], "http-export");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn default_provider_sends_headers_and_gzip() {
+        use crate::telemetry::otlp::{OtlpCompression, OtlpExportConfig};
+
+        let server = otlp_http_stub("/v1/traces").await;
+        let config = TelemetryConfig::new("test-service", "1.0.0")
+            .with_otlp_endpoint(server.uri())
+            .with_otlp_protocol(OtlpProtocol::HttpProtobuf)
+            .with_otlp_export(OtlpExportConfig::default().with_header("api-key", "general"))
+            .with_otlp_traces_export(
+                OtlpExportConfig::default()
+                    .with_header("api-key", "traces")
+                    .with_compression(OtlpCompression::Gzip),
+            );
+
+        export_span(DefaultProvider.build_tracer_provider(&config).await.unwrap()).await;
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests[0].headers.get("api-key").unwrap(), "traces");
+        assert_eq!(requests[0].headers.get("content-encoding").unwrap(), "gzip");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn default_provider_exports_metrics_over_http() {
+        use opentelemetry::metrics::MeterProvider;
+
+        let server = otlp_http_stub("/v1/metrics").await;
+        let config = TelemetryConfig::new("test-service", "1.0.0")
+            .with_otlp_endpoint(server.uri())
+            .with_otlp_protocol(OtlpProtocol::HttpProtobuf);
+
+        let provider = DefaultProvider.build_meter_provider(&config).await.unwrap();
+        provider.meter("test").u64_counter("hits").build().add(1, &[]);
+        tokio::task::spawn_blocking(move || provider.force_flush())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(!server.received_requests().await.unwrap().is_empty());
+    }
+
+    /// Private CA plus server and client certificates, written as PEM files
+    struct TestPki {
+        dir: tempfile::TempDir,
+        ca_pem: String,
+        server_identity: tonic::transport::Identity,
+    }
+
+    impl TestPki {
+        /// Server certificate name, distinct from the `127.0.0.1` endpoint host
+        const SERVER_NAME: &'static str = "collector.internal";
+
+        fn generate() -> Self {
+            use rcgen::{BasicConstraints, CertificateParams, CertifiedIssuer, IsCa, KeyPair};
+
+            let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+            ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+            let ca = CertifiedIssuer::self_signed(ca_params, KeyPair::generate().unwrap()).unwrap();
+
+            let issue = |name: &str| {
+                let key = KeyPair::generate().unwrap();
+                let cert = CertificateParams::new(vec![name.to_string()])
+                    .unwrap()
+                    .signed_by(&key, &ca)
+                    .unwrap();
+                (cert.pem(), key.serialize_pem())
+            };
+            let (server_cert, server_key) = issue(Self::SERVER_NAME);
+            let (client_cert, client_key) = issue("client");
+
+            let dir = tempfile::tempdir().unwrap();
+            std::fs::write(dir.path().join("ca.pem"), ca.pem()).unwrap();
+            std::fs::write(dir.path().join("client.pem"), client_cert).unwrap();
+            std::fs::write(dir.path().join("client.key"), client_key).unwrap();
+
+            Self {
+                dir,
+                ca_pem: ca.pem(),
+                server_identity: tonic::transport::Identity::from_pem(server_cert, server_key),
+            }
+        }
+
+        fn path(&self, name: &str) -> std::path::PathBuf {
+            self.dir.path().join(name)
+        }
+    }
+
+    /// OTLP/gRPC trace collector counting received spans
+    #[derive(Clone, Default)]
+    struct Collector {
+        spans: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[tonic::async_trait]
+    impl TraceService for Collector {
+        async fn export(
+            &self,
+            request: tonic::Request<ExportTraceServiceRequest>,
+        ) -> Result<tonic::Response<ExportTraceServiceResponse>, tonic::Status> {
+            let spans = request
+                .into_inner()
+                .resource_spans
+                .iter()
+                .flat_map(|rs| &rs.scope_spans)
+                .map(|ss| ss.spans.len())
+                .sum::<usize>();
+            self.spans
+                .fetch_add(spans, std::sync::atomic::Ordering::SeqCst);
+            Ok(tonic::Response::new(ExportTraceServiceResponse::default()))
+        }
+    }
+
+    /// Serve `collector` over TLS, requiring client certificates signed by the test CA
+    async fn start_mtls_collector(pki: &TestPki, collector: Collector) -> String {
+        use tonic::transport::{Certificate, Server, ServerTlsConfig};
+
+        let incoming =
+            tonic::transport::server::TcpIncoming::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let addr = incoming.local_addr().unwrap();
+        let tls = ServerTlsConfig::new()
+            .identity(pki.server_identity.clone())
+            .client_ca_root(Certificate::from_pem(&pki.ca_pem));
+
+        let server = Server::builder()
+            .tls_config(tls)
+            .unwrap()
+            .add_service(TraceServiceServer::new(collector));
+        tokio::spawn(server.serve_with_incoming(incoming));
+
+        format!("https://{}", addr)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn default_provider_exports_over_mutual_tls() {
+        let pki = TestPki::generate();
+        let collector = Collector::default();
+        let endpoint = start_mtls_collector(&pki, collector.clone()).await;
+        let config = TelemetryConfig::new("test-service", "1.0.0")
+            .with_otlp_endpoint(endpoint)
+            .with_otlp_tls(
+                OtlpTlsConfig::default()
+                    .with_ca_certificate(pki.path("ca.pem"))
+                    .with_client_identity(pki.path("client.pem"), pki.path("client.key"))
+                    .with_domain_name(TestPki::SERVER_NAME),
+            );
+
+        export_span(DefaultProvider.build_tracer_provider(&config).await.unwrap()).await;
+
+        assert_eq!(
+            collector.spans.load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn default_provider_mtls_rejects_missing_client_certificate() {
+        use opentelemetry::trace::{Tracer, TracerProvider};
+
+        let pki = TestPki::generate();
+        let collector = Collector::default();
+        let endpoint = start_mtls_collector(&pki, collector.clone()).await;
+        let config = TelemetryConfig::new("test-service", "1.0.0")
+            .with_otlp_endpoint(endpoint)
+            .with_otlp_tls(
+                OtlpTlsConfig::default()
+                    .with_ca_certificate(pki.path("ca.pem"))
+                    .with_domain_name(TestPki::SERVER_NAME),
+            );
+
+        let provider = DefaultProvider.build_tracer_provider(&config).await.unwrap();
+        provider.tracer("test").in_span("rejected", |_| {});
+        let flushed = tokio::task::spawn_blocking(move || provider.force_flush())
+            .await
+            .unwrap();
+
+        assert!(flushed.is_err());
+        assert_eq!(
+            collector.spans.load(std::sync::atomic::Ordering::SeqCst),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn default_provider_fails_on_missing_ca_file() {
+        let config = TelemetryConfig::new("test-service", "1.0.0")
+            .with_otlp_endpoint("https://localhost:4317")
+            .with_otlp_tls(OtlpTlsConfig::default().with_ca_certificate("/nonexistent/ca.pem"));
+
+        let result = DefaultProvider.build_tracer_provider(&config).await;
+
+        assert!(matches!(result, Err(TelemetryError::Config(_))));
+    }
+
     #[tokio::test]
     async fn default_provider_with_invalid_endpoint_succeeds_build() {
-        // Note: Invalid URL format doesn't fail at build time, only at runtime when connecting
+        // Providers don't validate; `init_with_config` rejects this via `TelemetryConfig::validate`
         let provider = DefaultProvider;
         let config = TelemetryConfig::new("test-service", "1.0.0")
             .with_otlp_endpoint("invalid-url");
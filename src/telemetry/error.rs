@@ -5,7 +5,33 @@ pub enum TelemetryError {
     Auth(String),
     Exporter(String),
     Config(String),
+    /// Every problem found by `TelemetryConfig::validate`
+    InvalidConfig(Vec<ConfigProblem>),
     Init(String),
+    Shutdown(String),
+}
+
+/// One invalid setting found by `TelemetryConfig::validate`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    /// Config field the problem is about, e.g. `otlp_endpoint`
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl ConfigProblem {
+    pub fn new(field: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            field,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
 }
 
 impl fmt::Display for TelemetryError {
@@ -14,7 +40,18 @@ impl fmt::Display for TelemetryError {
             Self::Auth(msg) => write!(f, "Authentication error: {}", msg),
             Self::Exporter(msg) => write!(f, "Exporter error: {}", msg),
             Self::Config(msg) => write!(f, "Configuration error: {}", msg),
+            Self::InvalidConfig(problems) => {
+                write!(f, "Invalid configuration: ")?;
+                for (i, problem) in problems.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}", problem)?;
+                }
+                Ok(())
+            }
             Self::Init(msg) => write!(f, "Initialization error: {}", msg),
+            Self::Shutdown(msg) => write!(f, "Shutdown error: {}", msg),
         }
     }
 }
@@ -25,4 +62,4 @@ impl From<opentelemetry_sdk::trace::TraceError> for TelemetryError {
     fn from(err: opentelemetry_sdk::trace::TraceError) -> Self {
         Self::Exporter(err.to_string())
     }
-}
\ No newline at end of file
+}
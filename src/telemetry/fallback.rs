@@ -0,0 +1,413 @@
+//! Keeping the service up when the telemetry backend can't start.
+//!
+//! With [`FailurePolicy::FallbackToLocal`] or [`FailurePolicy::RetryInBackground`],
+//! a backend that fails to initialize (typically GCP without usable
+//! credentials) no longer fails `init_with_config`. Instead:
+//!
+//! 1. Telemetry starts on the local pipeline: stdout logs as configured, plus
+//!    OTLP to `otlp_endpoint` under `FallbackToLocal`.
+//! 2. A warning carrying the [`TelemetryError`] is logged.
+//! 3. For GCP, a background task retries the Cloud Trace exporter with
+//!    exponential backoff and, once it builds, attaches it to the running
+//!    tracer provider through a [`DeferredProcessor`]. Custom backends (see
+//!    [`crate::telemetry::registry`]) are not retried.
+//!
+//! Only traces are attached later; metrics and logs stay on the local
+//! pipeline until the next restart.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use opentelemetry::Context;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::logs::SdkLoggerProvider;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::trace::{
+    BatchSpanProcessor, SdkTracerProvider, Span, SpanData, SpanProcessor,
+};
+use opentelemetry_sdk::Resource;
+use tracing::{debug, info};
+
+use crate::telemetry::api::{init_with_provider, TelemetryProvider};
+use crate::telemetry::config::{FailurePolicy, TelemetryBackend, TelemetryConfig, TracesExporter};
+use crate::telemetry::default::DefaultProvider;
+use crate::telemetry::error::TelemetryError;
+use crate::telemetry::fanout::with_secondary_backends;
+use crate::telemetry::guard::TelemetryGuard;
+
+/// Delay before the first retry; doubles after every failure
+pub const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Upper bound for the delay between retries
+pub const MAX_RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
+
+/// Span processor slot that drops spans until a processor is attached.
+///
+/// Clones share the slot.
+#[derive(Debug, Clone, Default)]
+pub struct DeferredProcessor {
+    slot: Arc<Slot>,
+}
+
+#[derive(Debug, Default)]
+struct Slot {
+    processor: RwLock<Option<BatchSpanProcessor>>,
+    shut_down: AtomicBool,
+}
+
+impl DeferredProcessor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start forwarding spans to `processor`.
+    ///
+    /// Returns `false`, and shuts `processor` down, if the slot was already shut down.
+    pub fn attach(&self, processor: BatchSpanProcessor) -> bool {
+        let mut slot = self.slot.processor.write().unwrap();
+        if self.is_shut_down() {
+            let _ = processor.shutdown();
+            return false;
+        }
+        *slot = Some(processor);
+        true
+    }
+
+    pub fn is_attached(&self) -> bool {
+        self.slot.processor.read().unwrap().is_some()
+    }
+
+    pub fn is_shut_down(&self) -> bool {
+        self.slot.shut_down.load(Ordering::SeqCst)
+    }
+}
+
+impl SpanProcessor for DeferredProcessor {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        if let Some(processor) = self.slot.processor.read().unwrap().as_ref() {
+            processor.on_start(span, cx);
+        }
+    }
+
+    fn on_end(&self, span: SpanData) {
+        if let Some(processor) = self.slot.processor.read().unwrap().as_ref() {
+            processor.on_end(span);
+        }
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        match self.slot.processor.read().unwrap().as_ref() {
+            Some(processor) => processor.force_flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        let processor = {
+            let mut slot = self.slot.processor.write().unwrap();
+            self.slot.shut_down.store(true, Ordering::SeqCst);
+            slot.take()
+        };
+        match processor {
+            Some(processor) => processor.shutdown_with_timeout(timeout),
+            None => Ok(()),
+        }
+    }
+
+    /// Attached processors bring their backend's own resource
+    fn set_resource(&mut self, _resource: &Resource) {}
+}
+
+/// Call `attempt` with exponential backoff until it yields a processor, then
+/// attach it. Stops early once `slot` is shut down.
+pub async fn retry_attach<F, Fut>(slot: DeferredProcessor, mut attempt: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<BatchSpanProcessor, TelemetryError>>,
+{
+    let mut delay = INITIAL_RETRY_DELAY;
+    loop {
+        tokio::time::sleep(delay).await;
+        if slot.is_shut_down() {
+            return;
+        }
+
+        match attempt().await {
+            Ok(processor) => {
+                if slot.attach(processor) {
+                    info!("Telemetry backend is available again; exporting traces to it");
+                }
+                return;
+            }
+            Err(e) => {
+                delay = (delay * 2).min(MAX_RETRY_DELAY);
+                debug!(error = %e, retry_in = ?delay, "Telemetry backend still unavailable");
+            }
+        }
+    }
+}
+
+/// Local pipeline with a [`DeferredProcessor`] for the backend's traces
+pub struct LocalFallback {
+    local: TelemetryConfig,
+    slot: DeferredProcessor,
+}
+
+impl LocalFallback {
+    /// Local pipeline for `config` under `config.failure_policy`.
+    ///
+    /// `backend_endpoint` is dropped as a local OTLP endpoint, since it only
+    /// accepts the backend's credentials.
+    pub fn new(
+        config: &TelemetryConfig,
+        backend_endpoint: Option<&str>,
+        slot: DeferredProcessor,
+    ) -> Self {
+        let mut local = config.clone().with_backend(TelemetryBackend::Local);
+        if config.failure_policy == Some(FailurePolicy::RetryInBackground) {
+            local.otlp_endpoint = None;
+            local.traces_exporter = TracesExporter::None;
+        }
+        if local.otlp_endpoint.is_some() && local.otlp_endpoint.as_deref() == backend_endpoint {
+            local.otlp_endpoint = None;
+        }
+
+        Self { local, slot }
+    }
+}
+
+impl TelemetryProvider for LocalFallback {
+    async fn build_tracer_provider(
+        &self,
+        _config: &TelemetryConfig,
+    ) -> Result<SdkTracerProvider, TelemetryError> {
+        let builder = DefaultProvider::tracer_provider_builder(&self.local)?
+            .with_span_processor(self.slot.clone());
+
+        Ok(with_secondary_backends(builder, &self.local).await.build())
+    }
+
+    async fn build_meter_provider(
+        &self,
+        _config: &TelemetryConfig,
+    ) -> Result<SdkMeterProvider, TelemetryError> {
+        DefaultProvider.build_meter_provider(&self.local).await
+    }
+
+    async fn build_logger_provider(
+        &self,
+        _config: &TelemetryConfig,
+    ) -> Result<SdkLoggerProvider, TelemetryError> {
+        DefaultProvider.build_logger_provider(&self.local).await
+    }
+}
+
+/// Initialize with `provider`, or with a [`LocalFallback`] if that fails and
+/// `config.failure_policy` allows it.
+///
+/// Returns the fallback's slot for the backend's processor, if one was started.
+pub(crate) async fn init_or_fallback<P: TelemetryProvider>(
+    provider: &P,
+    config: &TelemetryConfig,
+    backend_endpoint: Option<&str>,
+) -> Result<(TelemetryGuard, Option<DeferredProcessor>), TelemetryError> {
+    let policy = config.failure_policy.unwrap_or_default();
+    let error = match init_with_provider(provider, config).await {
+        Ok(guard) => return Ok((guard, None)),
+        Err(e) if policy == FailurePolicy::FailFast => return Err(e),
+        Err(e) => e,
+    };
+
+    let slot = DeferredProcessor::new();
+    let fallback = LocalFallback::new(config, backend_endpoint, slot.clone());
+    let guard = init_with_provider(&fallback, config).await?;
+
+    tracing::warn!(
+        error = %error,
+        policy = ?policy,
+        "Telemetry backend unavailable; running with local telemetry"
+    );
+
+    Ok((guard, Some(slot)))
+}
+
+/// Initialize with the GCP provider, falling back per `config.failure_policy`
+/// and retrying Cloud Trace in the background
+#[cfg(feature = "telemetry-gcp")]
+pub(crate) async fn init_gcp(
+    gcp_config: &crate::telemetry::gcp::GcpConfig,
+    config: &TelemetryConfig,
+) -> Result<TelemetryGuard, TelemetryError> {
+    use crate::telemetry::gcp::GcpProvider;
+
+    let provider = GcpProvider::new(gcp_config.clone());
+    let (guard, slot) = init_or_fallback(&provider, config, Some(&gcp_config.endpoint)).await?;
+    let Some(slot) = slot else {
+        return Ok(guard);
+    };
+
+    let provider = Arc::new(provider);
+    let config = config.clone();
+    tokio::spawn(retry_attach(slot, move || {
+        let provider = provider.clone();
+        let config = config.clone();
+        async move {
+            let exporter = provider.span_exporter(&config).await?;
+            let mut processor = BatchSpanProcessor::builder(exporter).build();
+            processor.set_resource(&provider.resource(&config).await);
+            Ok(processor)
+        }
+    }));
+
+    Ok(guard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{Tracer, TracerProvider};
+    use opentelemetry_sdk::trace::SpanExporter;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Clone, Default)]
+    struct CountingExporter {
+        exported: Arc<AtomicUsize>,
+    }
+
+    impl SpanExporter for CountingExporter {
+        async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+            self.exported.fetch_add(batch.len(), Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn batch(exporter: &CountingExporter) -> BatchSpanProcessor {
+        BatchSpanProcessor::builder(exporter.clone()).build()
+    }
+
+    #[test]
+    fn deferred_processor_forwards_only_once_attached() {
+        let slot = DeferredProcessor::new();
+        let provider = SdkTracerProvider::builder()
+            .with_span_processor(slot.clone())
+            .build();
+        let tracer = provider.tracer("test");
+        let exporter = CountingExporter::default();
+
+        tracer.in_span("before", |_| {});
+        assert!(slot.attach(batch(&exporter)));
+        tracer.in_span("after", |_| {});
+        provider.force_flush().unwrap();
+
+        assert!(slot.is_attached());
+        assert_eq!(exporter.exported.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn attach_after_shutdown_is_refused() {
+        let slot = DeferredProcessor::new();
+        slot.shutdown().unwrap();
+
+        assert!(!slot.attach(batch(&CountingExporter::default())));
+        assert!(!slot.is_attached());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retry_attach_backs_off_until_success() {
+        let slot = DeferredProcessor::new();
+        let exporter = CountingExporter::default();
+        let attempts = Arc::new(Mutex::new(Vec::new()));
+        let started = tokio::time::Instant::now();
+
+        retry_attach(slot.clone(), || {
+            let attempts = attempts.clone();
+            let exporter = exporter.clone();
+            async move {
+                let mut attempts = attempts.lock().unwrap();
+                attempts.push(started.elapsed());
+                if attempts.len() < 3 {
+                    Err(TelemetryError::Auth("no credentials".into()))
+                } else {
+                    Ok(batch(&exporter))
+                }
+            }
+        })
+        .await;
+
+        assert!(slot.is_attached());
+        assert_eq!(
+            *attempts.lock().unwrap(),
+            vec![
+                Duration::from_secs(5),
+                Duration::from_secs(15),
+                Duration::from_secs(35)
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retry_attach_stops_after_shutdown() {
+        let slot = DeferredProcessor::new();
+        slot.shutdown().unwrap();
+        let attempted = Arc::new(AtomicBool::new(false));
+
+        retry_attach(slot, || {
+            attempted.store(true, Ordering::SeqCst);
+            async { Err(TelemetryError::Auth("no credentials".into())) }
+        })
+        .await;
+
+        assert!(!attempted.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn local_fallback_keeps_or_drops_local_exporters_by_policy() {
+        let config = TelemetryConfig::new("svc", "1.0")
+            .with_otlp_endpoint("http://collector:4317")
+            .with_traces_exporter(TracesExporter::Console)
+            .with_failure_policy(FailurePolicy::FallbackToLocal);
+
+        let fallback = LocalFallback::new(&config, None, DeferredProcessor::new());
+        assert_eq!(
+            fallback.local.otlp_endpoint.as_deref(),
+            Some("http://collector:4317")
+        );
+        assert_eq!(fallback.local.traces_exporter, TracesExporter::Console);
+        assert_eq!(fallback.local.backend, TelemetryBackend::Local);
+
+        let backend_endpoint = LocalFallback::new(
+            &config,
+            Some("http://collector:4317"),
+            DeferredProcessor::new(),
+        );
+        assert_eq!(backend_endpoint.local.otlp_endpoint, None);
+
+        let retry = LocalFallback::new(
+            &config
+                .clone()
+                .with_failure_policy(FailurePolicy::RetryInBackground),
+            None,
+            DeferredProcessor::new(),
+        );
+        assert_eq!(retry.local.otlp_endpoint, None);
+        assert_eq!(retry.local.traces_exporter, TracesExporter::None);
+    }
+
+    #[tokio::test]
+    async fn local_fallback_feeds_attached_backend() {
+        let slot = DeferredProcessor::new();
+        let config = TelemetryConfig::new("svc", "1.0");
+        let fallback = LocalFallback::new(&config, None, slot.clone());
+        let provider = fallback.build_tracer_provider(&config).await.unwrap();
+        let exporter = CountingExporter::default();
+
+        slot.attach(batch(&exporter));
+        provider.tracer("test").in_span("work", |_| {});
+        provider.force_flush().unwrap();
+
+        assert_eq!(exporter.exported.load(Ordering::SeqCst), 1);
+    }
+}
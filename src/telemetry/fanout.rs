@@ -0,0 +1,654 @@
+//! Sending spans to more than one backend at once.
+//!
+//! Each [`SecondaryBackend`] in `TelemetryConfig::secondary_backends` becomes
+//! an extra span processor on the main backend's tracer provider, with its own
+//! batch queue and export thread:
+//!
+//! - A slow or unreachable secondary only fills (and then drops from) its own
+//!   queue; ending a span never waits on it.
+//! - The main backend's processor comes first, so it is flushed and shut down
+//!   before any secondary.
+//! - A secondary that fails to build (bad endpoint, no GCP credentials, ...)
+//!   is skipped instead of failing initialization. The warning is logged
+//!   once the subscriber is installed (see [`collect_skipped_backends`]).
+//!
+//! Spans are first sampled by `TelemetryConfig::sampler`; a secondary's own
+//! `sampler` then keeps a subset of those. It decides by trace ID alone, so a
+//! ratio sampler keeps or drops whole traces. A secondary never sees spans the
+//! main sampler dropped, so validation rejects a secondary sampler that keeps a
+//! larger fraction than the main one: to send everything to a debug backend,
+//! sample everything and give the main backend the lower ratio as a secondary.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::time::Duration;
+
+use opentelemetry::trace::SamplingDecision;
+use opentelemetry::Context;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::trace::{
+    BatchSpanProcessor, Sampler, ShouldSample, Span, SpanData, SpanProcessor, TracerProviderBuilder,
+};
+use opentelemetry_sdk::Resource;
+use serde::Deserialize;
+
+use crate::telemetry::config::{check_endpoint, OtlpProtocol, TelemetryBackend, TelemetryConfig};
+use crate::telemetry::error::{ConfigProblem, TelemetryError};
+use crate::telemetry::otlp::{OtlpExportConfig, OtlpTlsConfig};
+use crate::telemetry::sampling::SamplerConfig;
+
+/// Extra span destination, fed alongside the main backend
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SecondaryBackend {
+    /// `Local` exports OTLP to `otlp_endpoint`; `Gcp` exports to Cloud Trace
+    pub backend: TelemetryBackend,
+    /// Collector for a `Local` backend
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
+    /// Defaults to the main `otlp_protocol`
+    #[serde(default)]
+    pub otlp_protocol: Option<OtlpProtocol>,
+    /// Falls back to the main trace export settings field by field
+    #[serde(default)]
+    pub otlp_export: OtlpExportConfig,
+    /// For a `Local` backend, defaults to the main `otlp_tls`; other backends
+    /// only use TLS settings given here
+    #[serde(default)]
+    pub otlp_tls: Option<OtlpTlsConfig>,
+    /// Keeps a subset of the spans the main sampler records; `None` keeps all.
+    /// Can't keep a larger fraction than the main sampler.
+    #[serde(default)]
+    pub sampler: Option<SamplerConfig>,
+}
+
+impl SecondaryBackend {
+    pub fn new(backend: TelemetryBackend) -> Self {
+        Self {
+            backend,
+            otlp_endpoint: None,
+            otlp_protocol: None,
+            otlp_export: OtlpExportConfig::default(),
+            otlp_tls: None,
+            sampler: None,
+        }
+    }
+
+    /// OTLP collector at `endpoint`
+    pub fn otlp(endpoint: impl Into<String>) -> Self {
+        Self::new(TelemetryBackend::Local).with_otlp_endpoint(endpoint)
+    }
+
+    pub fn with_otlp_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.otlp_endpoint = Some(endpoint.into());
+        self
+    }
+
+    pub fn with_otlp_protocol(mut self, protocol: OtlpProtocol) -> Self {
+        self.otlp_protocol = Some(protocol);
+        self
+    }
+
+    pub fn with_otlp_export(mut self, export: OtlpExportConfig) -> Self {
+        self.otlp_export = export;
+        self
+    }
+
+    pub fn with_otlp_tls(mut self, tls: OtlpTlsConfig) -> Self {
+        self.otlp_tls = Some(tls);
+        self
+    }
+
+    pub fn with_sampler(mut self, sampler: SamplerConfig) -> Self {
+        self.sampler = Some(sampler);
+        self
+    }
+
+    /// Record problems with the endpoint, backend settings or sampler, given
+    /// the main config
+    pub fn validate(&self, main: &TelemetryConfig, problems: &mut Vec<ConfigProblem>) {
+        match (&self.backend, &self.otlp_endpoint) {
+            (TelemetryBackend::Local, Some(endpoint)) => {
+                check_endpoint("secondary_backends.otlp_endpoint", endpoint, problems)
+            }
+            (TelemetryBackend::Local, None) => problems.push(ConfigProblem::new(
+                "secondary_backends.otlp_endpoint",
+                "a local secondary backend needs an endpoint",
+            )),
+            #[cfg(feature = "telemetry-gcp")]
+            (TelemetryBackend::Gcp(gcp_config), _) => gcp_config.validate(problems),
+            #[cfg(feature = "telemetry-aws")]
+            (TelemetryBackend::Aws(aws_config), _) => {
+                aws_config.validate(problems);
+                // Trace IDs come from the main provider, and X-Ray drops
+                // segments whose IDs don't carry a recent timestamp
+                if !matches!(main.backend, TelemetryBackend::Aws(_)) {
+                    problems.push(ConfigProblem::new(
+                        "secondary_backends.backend",
+                        "an AWS secondary backend needs an AWS main backend for X-Ray trace IDs",
+                    ));
+                }
+            }
+            #[cfg(feature = "telemetry-zipkin")]
+            (TelemetryBackend::Zipkin(zipkin_config), _) => zipkin_config.validate(problems),
+            (TelemetryBackend::Custom(custom), _) => problems.push(ConfigProblem::new(
+                "secondary_backends.backend",
+                format!("custom backend {:?} can't be a secondary backend", custom.name()),
+            )),
+        }
+        if let Some(
+            SamplerConfig::TraceIdRatio(ratio) | SamplerConfig::ParentBasedTraceIdRatio(ratio),
+        ) = self.sampler
+        {
+            if !(0.0..=1.0).contains(&ratio) {
+                problems.push(ConfigProblem::new(
+                    "secondary_backends.sampler",
+                    format!("ratio {} is outside 0.0..=1.0", ratio),
+                ));
+            }
+        }
+        if let Some(sampler) = self.sampler {
+            if sampler.root_ratio() > main.sampler.root_ratio() {
+                problems.push(ConfigProblem::new(
+                    "secondary_backends.sampler",
+                    format!(
+                        "keeps {} of traces, more than the main sampler's {}; \
+                         secondaries only see spans the main sampler keeps",
+                        sampler.root_ratio(),
+                        main.sampler.root_ratio()
+                    ),
+                ));
+            }
+        }
+    }
+
+    /// The main config with this backend's exporter settings applied
+    fn exporter_config(&self, main: &TelemetryConfig) -> TelemetryConfig {
+        let mut config = main.clone();
+        config.otlp_endpoint = self.otlp_endpoint.clone();
+        config.otlp_protocol = self.otlp_protocol.unwrap_or(main.otlp_protocol);
+        config.otlp_traces_export = self.otlp_export.or(&main.trace_export());
+        // The main settings describe the main collector, so only another OTLP
+        // collector inherits them
+        config.otlp_tls = match (&self.otlp_tls, &self.backend) {
+            (Some(tls), _) => tls.clone(),
+            (None, TelemetryBackend::Local) => main.otlp_tls.clone(),
+            (None, _) => OtlpTlsConfig::default(),
+        };
+        config.backend = self.backend.clone();
+        config
+    }
+
+    /// Short description for log messages
+    fn describe(&self) -> String {
+        match (&self.backend, &self.otlp_endpoint) {
+            (TelemetryBackend::Local, Some(endpoint)) => format!("OTLP {}", endpoint),
+            (TelemetryBackend::Local, None) => "OTLP (no endpoint)".to_string(),
+            #[cfg(feature = "telemetry-gcp")]
+            (TelemetryBackend::Gcp(gcp_config), _) => {
+                format!("Cloud Trace ({})", gcp_config.project_id)
+            }
+            #[cfg(feature = "telemetry-aws")]
+            (TelemetryBackend::Aws(aws_config), _) => format!("X-Ray via {}", aws_config.endpoint),
+            #[cfg(feature = "telemetry-zipkin")]
+            (TelemetryBackend::Zipkin(zipkin_config), _) => {
+                format!("Zipkin {}", zipkin_config.endpoint)
+            }
+            (TelemetryBackend::Custom(custom), _) => format!("custom backend {}", custom.name()),
+        }
+    }
+
+    /// Build this backend's processor
+    async fn build_processor(
+        &self,
+        main: &TelemetryConfig,
+    ) -> Result<SecondaryProcessor, TelemetryError> {
+        let config = self.exporter_config(main);
+
+        let (processor, resource) = match &self.backend {
+            TelemetryBackend::Local => {
+                let endpoint = config.otlp_endpoint.as_deref().ok_or_else(|| {
+                    TelemetryError::Config(
+                        "A local secondary backend needs an otlp_endpoint".into(),
+                    )
+                })?;
+                let exporter =
+                    crate::telemetry::default::exporter::build_span_exporter(endpoint, &config)?;
+                // Share the main backend's resource
+                (BatchSpanProcessor::builder(exporter).build(), None)
+            }
+            #[cfg(feature = "telemetry-gcp")]
+            TelemetryBackend::Gcp(gcp_config) => {
+                let provider = crate::telemetry::gcp::GcpProvider::new(gcp_config.clone());
+                let exporter = provider.span_exporter(&config).await?;
+                let resource = provider.resource(&config).await;
+                (
+                    BatchSpanProcessor::builder(exporter).build(),
+                    Some(resource),
+                )
+            }
+            #[cfg(feature = "telemetry-aws")]
+            TelemetryBackend::Aws(aws_config) => {
+                // Trace IDs come from the main backend's generator, so
+                // validation only allows this under an AWS main backend
+                let provider = crate::telemetry::aws::AwsProvider::new(aws_config.clone());
+                let exporter = provider.span_exporter(&config)?;
+                let resource = provider.resource(&config).await;
+                (
+                    BatchSpanProcessor::builder(exporter).build(),
+                    Some(resource),
+                )
+            }
+            #[cfg(feature = "telemetry-zipkin")]
+            TelemetryBackend::Zipkin(zipkin_config) => {
+                let exporter =
+                    crate::telemetry::zipkin::ZipkinExporter::new(&zipkin_config.endpoint)?;
+                // Share the main backend's resource
+                (BatchSpanProcessor::builder(exporter).build(), None)
+            }
+            TelemetryBackend::Custom(custom) => {
+                return Err(TelemetryError::Config(format!(
+                    "Custom backend {:?} can't be a secondary backend",
+                    custom.name()
+                )))
+            }
+        };
+
+        Ok(SecondaryProcessor::new(
+            processor,
+            self.sampler.map(SamplerConfig::to_sampler),
+            resource,
+        ))
+    }
+}
+
+/// Add a processor per `config.secondary_backends`, skipping any that fail to build.
+///
+/// Call after adding the main backend's processor, so that it comes first.
+pub async fn with_secondary_backends(
+    mut builder: TracerProviderBuilder,
+    config: &TelemetryConfig,
+) -> TracerProviderBuilder {
+    for backend in &config.secondary_backends {
+        match backend.build_processor(config).await {
+            Ok(processor) => builder = builder.with_span_processor(processor),
+            Err(error) => SkippedBackend {
+                backend: backend.describe(),
+                error,
+            }
+            .defer_or_report(),
+        }
+    }
+    builder
+}
+
+tokio::task_local! {
+    /// Backends skipped inside [`collect_skipped_backends`]
+    static SKIPPED: RefCell<Vec<SkippedBackend>>;
+}
+
+/// Secondary backend that failed to build
+#[derive(Debug)]
+pub(crate) struct SkippedBackend {
+    backend: String,
+    error: TelemetryError,
+}
+
+impl SkippedBackend {
+    pub(crate) fn report(&self) {
+        tracing::warn!(
+            backend = %self.backend,
+            error = %self.error,
+            "Skipping secondary telemetry backend"
+        );
+    }
+
+    /// Hold for [`collect_skipped_backends`], or report now outside it
+    fn defer_or_report(self) {
+        if SKIPPED.try_with(|_| ()).is_ok() {
+            SKIPPED.with(|skipped| skipped.borrow_mut().push(self));
+        } else {
+            self.report();
+        }
+    }
+}
+
+/// Run `build`, returning the secondary backends it skipped instead of
+/// logging them, since the subscriber isn't installed while providers are built
+pub(crate) async fn collect_skipped_backends<F: Future>(
+    build: F,
+) -> (F::Output, Vec<SkippedBackend>) {
+    SKIPPED
+        .scope(RefCell::new(Vec::new()), async move {
+            let output = build.await;
+            (output, SKIPPED.with(RefCell::take))
+        })
+        .await
+}
+
+/// Batch processor for one secondary backend, with an optional extra sampler
+/// and its own resource
+#[derive(Debug)]
+pub struct SecondaryProcessor {
+    inner: BatchSpanProcessor,
+    sampler: Option<Sampler>,
+    /// Overrides the provider's resource, e.g. GCP attributes under a local main backend
+    resource: Option<Resource>,
+}
+
+impl SecondaryProcessor {
+    pub fn new(
+        inner: BatchSpanProcessor,
+        sampler: Option<Sampler>,
+        resource: Option<Resource>,
+    ) -> Self {
+        Self {
+            inner,
+            sampler,
+            resource,
+        }
+    }
+
+    fn keeps(&self, span: &SpanData) -> bool {
+        let Some(sampler) = &self.sampler else {
+            return true;
+        };
+
+        // No parent context: parent-based samplers fall through to their root
+        // sampler, which decides from the trace ID
+        let result = sampler.should_sample(
+            None,
+            span.span_context.trace_id(),
+            &span.name,
+            &span.span_kind,
+            &span.attributes,
+            &span.links.links,
+        );
+        result.decision == SamplingDecision::RecordAndSample
+    }
+}
+
+impl SpanProcessor for SecondaryProcessor {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        self.inner.on_start(span, cx);
+    }
+
+    fn on_end(&self, span: SpanData) {
+        if self.keeps(&span) {
+            self.inner.on_end(span);
+        }
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner
+            .set_resource(self.resource.as_ref().unwrap_or(resource));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::registry::CustomBackend;
+    use opentelemetry::trace::{Tracer, TracerProvider};
+    use opentelemetry_sdk::trace::{BatchConfigBuilder, SdkTracerProvider, SpanExporter};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    /// Counts exported spans, optionally sleeping on every export
+    #[derive(Debug, Clone, Default)]
+    struct CountingExporter {
+        exported: Arc<AtomicUsize>,
+        delay: Option<Duration>,
+        resource: Arc<Mutex<Option<Resource>>>,
+    }
+
+    impl SpanExporter for CountingExporter {
+        async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+            if let Some(delay) = self.delay {
+                std::thread::sleep(delay);
+            }
+            self.exported.fetch_add(batch.len(), Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn set_resource(&mut self, resource: &Resource) {
+            *self.resource.lock().unwrap() = Some(resource.clone());
+        }
+    }
+
+    fn secondary(exporter: CountingExporter, sampler: Option<SamplerConfig>) -> SecondaryProcessor {
+        SecondaryProcessor::new(
+            BatchSpanProcessor::builder(exporter).build(),
+            sampler.map(SamplerConfig::to_sampler),
+            None,
+        )
+    }
+
+    fn emit_traces(provider: &SdkTracerProvider, count: usize) {
+        let tracer = provider.tracer("test");
+        for _ in 0..count {
+            tracer.in_span("root", |_| tracer.in_span("child", |_| {}));
+        }
+    }
+
+    #[test]
+    fn secondary_sampler_keeps_a_subset_of_whole_traces() {
+        let primary = CountingExporter::default();
+        let all = CountingExporter::default();
+        let none = CountingExporter::default();
+        let ratio = CountingExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(primary.clone())
+            .with_span_processor(secondary(all.clone(), None))
+            .with_span_processor(secondary(none.clone(), Some(SamplerConfig::AlwaysOff)))
+            .with_span_processor(secondary(
+                ratio.clone(),
+                Some(SamplerConfig::ParentBasedTraceIdRatio(0.5)),
+            ))
+            .build();
+
+        emit_traces(&provider, 200);
+        provider.force_flush().unwrap();
+
+        assert_eq!(primary.exported.load(Ordering::SeqCst), 400);
+        assert_eq!(all.exported.load(Ordering::SeqCst), 400);
+        assert_eq!(none.exported.load(Ordering::SeqCst), 0);
+        let kept = ratio.exported.load(Ordering::SeqCst);
+        assert!(kept % 2 == 0, "traces were split: {}", kept);
+        assert!((100..300).contains(&kept), "kept {} of 400", kept);
+    }
+
+    #[test]
+    fn slow_secondary_does_not_block_primary() {
+        let primary = CountingExporter::default();
+        let slow = CountingExporter {
+            delay: Some(Duration::from_secs(60)),
+            ..CountingExporter::default()
+        };
+        let slow_processor = SecondaryProcessor::new(
+            BatchSpanProcessor::builder(slow.clone())
+                .with_batch_config(
+                    BatchConfigBuilder::default()
+                        .with_max_queue_size(4)
+                        .with_max_export_batch_size(1)
+                        .with_scheduled_delay(Duration::from_millis(1))
+                        .build(),
+                )
+                .build(),
+            None,
+            None,
+        );
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(primary.clone())
+            .with_span_processor(slow_processor)
+            .build();
+
+        let started = Instant::now();
+        emit_traces(&provider, 500);
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(primary.exported.load(Ordering::SeqCst), 1000);
+        assert_eq!(slow.exported.load(Ordering::SeqCst), 0);
+        // Abandon the stuck export thread
+        let _ = provider.shutdown_with_timeout(Duration::from_millis(50));
+    }
+
+    #[test]
+    fn secondary_resource_overrides_provider_resource() {
+        let exporter = CountingExporter::default();
+        let resource = Resource::builder_empty()
+            .with_attribute(opentelemetry::KeyValue::new("gcp.project_id", "my-project"))
+            .build();
+        let provider = SdkTracerProvider::builder()
+            .with_span_processor(SecondaryProcessor::new(
+                BatchSpanProcessor::builder(exporter.clone()).build(),
+                None,
+                Some(resource.clone()),
+            ))
+            .build();
+
+        emit_traces(&provider, 1);
+        provider.force_flush().unwrap();
+
+        assert_eq!(exporter.resource.lock().unwrap().as_ref(), Some(&resource));
+    }
+
+    #[tokio::test]
+    async fn unbuildable_secondary_is_skipped() {
+        let config = TelemetryConfig::new("svc", "1.0")
+            .with_secondary_backend(SecondaryBackend::new(TelemetryBackend::Local));
+        assert!(matches!(
+            config.validate(),
+            Err(TelemetryError::InvalidConfig(_))
+        ));
+
+        let (builder, skipped) = collect_skipped_backends(with_secondary_backends(
+            SdkTracerProvider::builder(),
+            &config,
+        ))
+        .await;
+        let provider = builder.build();
+
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].backend, "OTLP (no endpoint)");
+
+        emit_traces(&provider, 1);
+        assert!(provider.force_flush().is_ok());
+    }
+
+    #[test]
+    fn secondary_backends_load_from_config_file() {
+        let config: TelemetryConfig = toml::from_str(
+            r#"
+            [[secondary_backends]]
+            backend = "local"
+            otlp_endpoint = "http://collector:4318"
+            otlp_protocol = "http/protobuf"
+            sampler = { name = "traceidratio", ratio = 0.1 }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.secondary_backends,
+            vec![SecondaryBackend::otlp("http://collector:4318")
+                .with_otlp_protocol(OtlpProtocol::HttpProtobuf)
+                .with_sampler(SamplerConfig::TraceIdRatio(0.1))]
+        );
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn exporter_config_overrides_main_settings() {
+        let main = TelemetryConfig::new("svc", "1.0")
+            .with_otlp_endpoint("http://primary:4317")
+            .with_otlp_export(
+                OtlpExportConfig::default()
+                    .with_header("x-team", "core")
+                    .with_timeout(Duration::from_secs(3)),
+            );
+        let backend = SecondaryBackend::otlp("http://secondary:4318")
+            .with_otlp_protocol(OtlpProtocol::HttpProtobuf)
+            .with_otlp_export(OtlpExportConfig::default().with_timeout(Duration::from_secs(1)));
+
+        let config = backend.exporter_config(&main);
+
+        assert_eq!(
+            config.otlp_endpoint.as_deref(),
+            Some("http://secondary:4318")
+        );
+        assert_eq!(config.otlp_protocol, OtlpProtocol::HttpProtobuf);
+        assert_eq!(
+            config.trace_export(),
+            OtlpExportConfig::default()
+                .with_header("x-team", "core")
+                .with_timeout(Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn only_otlp_secondaries_inherit_main_tls() {
+        let main = TelemetryConfig::new("svc", "1.0").with_otlp_tls(
+            OtlpTlsConfig::default()
+                .with_ca_certificate("/etc/otel/ca.pem")
+                .with_insecure(true),
+        );
+
+        let otlp = SecondaryBackend::otlp("http://secondary:4317").exporter_config(&main);
+        assert_eq!(otlp.otlp_tls, main.otlp_tls);
+
+        let custom = SecondaryBackend::new(TelemetryBackend::Custom(CustomBackend::new(
+            "vendor",
+            crate::telemetry::testing::InMemoryProvider::new(),
+        )))
+        .exporter_config(&main);
+        assert_eq!(custom.otlp_tls, OtlpTlsConfig::default());
+    }
+
+    #[test]
+    fn secondary_sampler_cannot_exceed_main_sampler() {
+        let main = TelemetryConfig::new("svc", "1.0")
+            .with_sampler(SamplerConfig::ParentBasedTraceIdRatio(0.1));
+        let debug =
+            SecondaryBackend::otlp("http://debug:4317").with_sampler(SamplerConfig::AlwaysOn);
+        let sampled = SecondaryBackend::otlp("http://debug:4317")
+            .with_sampler(SamplerConfig::TraceIdRatio(0.05));
+
+        let mut problems = Vec::new();
+        debug.validate(&main, &mut problems);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].field, "secondary_backends.sampler");
+
+        let mut problems = Vec::new();
+        sampled.validate(&main, &mut problems);
+        assert!(problems.is_empty());
+    }
+
+    #[cfg(feature = "telemetry-aws")]
+    #[test]
+    fn aws_secondary_requires_aws_main_backend() {
+        use crate::telemetry::aws::AwsConfig;
+
+        let backend = SecondaryBackend::new(TelemetryBackend::Aws(AwsConfig::new()));
+
+        let mut problems = Vec::new();
+        backend.validate(&TelemetryConfig::new("svc", "1.0"), &mut problems);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].field, "secondary_backends.backend");
+
+        let main = TelemetryConfig::new("svc", "1.0")
+            .with_backend(TelemetryBackend::Aws(AwsConfig::new().with_region("us-east-1")));
+        let mut problems = Vec::new();
+        backend.validate(&main, &mut problems);
+        assert!(problems.is_empty());
+    }
+}
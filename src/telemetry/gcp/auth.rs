@@ -1,42 +1,303 @@
-use tonic::metadata::{MetadataMap, MetadataValue};
+use std::future::Future;
+use std::sync::{Arc, RwLock, Weak};
+use std::time::{Duration, SystemTime};
+
+use tonic::metadata::{Ascii, MetadataValue};
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
 
 use crate::telemetry::error::TelemetryError;
 
 const TRACE_SCOPE: &str = "https://www.googleapis.com/auth/trace.append";
+const MONITORING_SCOPE: &str = "https://www.googleapis.com/auth/monitoring.write";
+const LOGGING_SCOPE: &str = "https://www.googleapis.com/auth/logging.write";
 
-/// GCP authentication metadata for OTLP requests
-pub struct GcpAuth {
-    pub metadata: MetadataMap,
+/// Scopes requested for the shared token (traces + metrics + logs)
+const SCOPES: &[&str] = &[TRACE_SCOPE, MONITORING_SCOPE, LOGGING_SCOPE];
+
+/// Refresh tokens this long before they expire
+pub const REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+
+/// Lower bound between two refreshes, so short-lived or cached tokens don't spin the loop
+pub const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Delay before retrying after a failed refresh
+pub const RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// An OAuth2 access token and its remaining lifetime
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessToken {
+    pub value: String,
+    pub expires_in: Duration,
 }
 
-impl GcpAuth {
-    /// Create auth metadata from Application Default Credentials
-    pub async fn from_adc(project_id: &str) -> Result<Self, TelemetryError> {
+/// Source of OAuth2 access tokens (ADC in production, fakes in tests)
+pub trait TokenSource: Send + Sync + 'static {
+    /// Fetch a currently valid token
+    fn fetch_token(&self) -> impl Future<Output = Result<AccessToken, TelemetryError>> + Send;
+}
+
+/// Token source backed by Application Default Credentials
+pub struct AdcTokenSource {
+    provider: Arc<dyn gcp_auth::TokenProvider>,
+}
+
+impl AdcTokenSource {
+    pub async fn new() -> Result<Self, TelemetryError> {
         let provider = gcp_auth::provider()
             .await
             .map_err(|e| TelemetryError::Auth(format!("Failed to create auth provider: {}", e)))?;
 
-        let token = provider
-            .token(&[TRACE_SCOPE])
+        Ok(Self { provider })
+    }
+}
+
+impl TokenSource for AdcTokenSource {
+    async fn fetch_token(&self) -> Result<AccessToken, TelemetryError> {
+        let token = self
+            .provider
+            .token(SCOPES)
             .await
             .map_err(|e| TelemetryError::Auth(format!("Failed to get token: {}", e)))?;
 
-        let mut metadata = MetadataMap::new();
+        let expires_in = SystemTime::from(token.expires_at())
+            .duration_since(SystemTime::now())
+            .unwrap_or_default();
+
+        Ok(AccessToken {
+            value: token.as_str().to_string(),
+            expires_in,
+        })
+    }
+}
+
+/// GCP authentication for OTLP requests.
+///
+/// Acts as a tonic interceptor: every export reads the current bearer token,
+/// which a background task keeps fresh until the last clone is dropped.
+#[derive(Clone)]
+pub struct GcpAuth {
+    authorization: Arc<RwLock<MetadataValue<Ascii>>>,
+    user_project: Option<MetadataValue<Ascii>>,
+}
+
+impl GcpAuth {
+    /// Create auth from Application Default Credentials
+    pub async fn from_adc(project_id: &str) -> Result<Self, TelemetryError> {
+        Self::from_source(AdcTokenSource::new().await?, project_id).await
+    }
 
-        metadata.insert(
-            "authorization",
-            MetadataValue::try_from(format!("Bearer {}", token.as_str()))
-                .map_err(|e| TelemetryError::Auth(format!("Invalid token format: {}", e)))?,
-        );
+    /// Create auth from any token source.
+    ///
+    /// The first token is fetched eagerly so misconfigured credentials fail here.
+    pub async fn from_source<S: TokenSource>(
+        source: S,
+        project_id: &str,
+    ) -> Result<Self, TelemetryError> {
+        let token = source.fetch_token().await?;
 
-        if !project_id.is_empty() {
-            metadata.insert(
-                "x-goog-user-project",
+        let user_project = if project_id.is_empty() {
+            None
+        } else {
+            Some(
                 MetadataValue::try_from(project_id)
                     .map_err(|e| TelemetryError::Auth(format!("Invalid project ID: {}", e)))?,
-            );
+            )
+        };
+
+        let authorization = Arc::new(RwLock::new(bearer(&token)?));
+
+        tokio::spawn(refresh_loop(
+            source,
+            Arc::downgrade(&authorization),
+            refresh_delay(token.expires_in),
+        ));
+
+        Ok(Self {
+            authorization,
+            user_project,
+        })
+    }
+
+    /// Current `authorization` header value
+    pub fn authorization(&self) -> MetadataValue<Ascii> {
+        self.authorization
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl Interceptor for GcpAuth {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let metadata = request.metadata_mut();
+        metadata.insert("authorization", self.authorization());
+        if let Some(project) = &self.user_project {
+            metadata.insert("x-goog-user-project", project.clone());
+        }
+        Ok(request)
+    }
+}
+
+fn bearer(token: &AccessToken) -> Result<MetadataValue<Ascii>, TelemetryError> {
+    MetadataValue::try_from(format!("Bearer {}", token.value))
+        .map_err(|e| TelemetryError::Auth(format!("Invalid token format: {}", e)))
+}
+
+/// How long to wait before refreshing a token with the given lifetime
+fn refresh_delay(expires_in: Duration) -> Duration {
+    expires_in
+        .saturating_sub(REFRESH_MARGIN)
+        .max(MIN_REFRESH_INTERVAL)
+}
+
+/// Keep `slot` filled with a fresh token until every `GcpAuth` clone is dropped.
+///
+/// `gcp_auth` caches tokens until shortly before expiry, so a refresh inside the
+/// margin may hand back the same token; the loop then polls at `MIN_REFRESH_INTERVAL`
+/// until a new one is issued.
+async fn refresh_loop<S: TokenSource>(
+    source: S,
+    slot: Weak<RwLock<MetadataValue<Ascii>>>,
+    mut delay: Duration,
+) {
+    loop {
+        tokio::time::sleep(delay).await;
+
+        if slot.strong_count() == 0 {
+            return;
         }
 
-        Ok(Self { metadata })
+        let refreshed = source
+            .fetch_token()
+            .await
+            .and_then(|token| Ok((bearer(&token)?, token.expires_in)));
+
+        let Some(slot) = slot.upgrade() else {
+            return;
+        };
+
+        delay = match refreshed {
+            Ok((value, expires_in)) => {
+                *slot.write().unwrap_or_else(|e| e.into_inner()) = value;
+                refresh_delay(expires_in)
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to refresh GCP access token");
+                RETRY_INTERVAL
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    /// Hands out queued tokens, then fails once exhausted
+    struct FakeTokenSource {
+        tokens: Mutex<VecDeque<Result<AccessToken, TelemetryError>>>,
+    }
+
+    impl FakeTokenSource {
+        fn new(tokens: Vec<Result<AccessToken, TelemetryError>>) -> Self {
+            Self {
+                tokens: Mutex::new(tokens.into()),
+            }
+        }
+    }
+
+    impl TokenSource for FakeTokenSource {
+        async fn fetch_token(&self) -> Result<AccessToken, TelemetryError> {
+            self.tokens
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or_else(|| Err(TelemetryError::Auth("exhausted".to_string())))
+        }
+    }
+
+    fn token(value: &str, expires_in: Duration) -> Result<AccessToken, TelemetryError> {
+        Ok(AccessToken {
+            value: value.to_string(),
+            expires_in,
+        })
+    }
+
+    fn intercept(auth: &GcpAuth) -> Request<()> {
+        auth.clone().call(Request::new(())).unwrap()
+    }
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    #[test]
+    fn refresh_delay_subtracts_margin() {
+        assert_eq!(refresh_delay(HOUR), HOUR - REFRESH_MARGIN);
+    }
+
+    #[test]
+    fn refresh_delay_has_lower_bound() {
+        assert_eq!(refresh_delay(Duration::ZERO), MIN_REFRESH_INTERVAL);
+        assert_eq!(refresh_delay(REFRESH_MARGIN), MIN_REFRESH_INTERVAL);
+    }
+
+    #[tokio::test]
+    async fn from_source_fails_when_first_token_fails() {
+        let source = FakeTokenSource::new(vec![]);
+
+        let result = GcpAuth::from_source(source, "proj").await;
+
+        assert!(matches!(result, Err(TelemetryError::Auth(_))));
+    }
+
+    #[tokio::test]
+    async fn interceptor_injects_authorization_and_project() {
+        let source = FakeTokenSource::new(vec![token("first", HOUR)]);
+        let auth = GcpAuth::from_source(source, "my-project").await.unwrap();
+
+        let request = intercept(&auth);
+
+        assert_eq!(request.metadata().get("authorization").unwrap(), "Bearer first");
+        assert_eq!(request.metadata().get("x-goog-user-project").unwrap(), "my-project");
+    }
+
+    #[tokio::test]
+    async fn interceptor_omits_empty_project() {
+        let source = FakeTokenSource::new(vec![token("first", HOUR)]);
+        let auth = GcpAuth::from_source(source, "").await.unwrap();
+
+        let request = intercept(&auth);
+
+        assert!(request.metadata().get("x-goog-user-project").is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rotated_token_is_used_after_refresh() {
+        let source = FakeTokenSource::new(vec![token("first", HOUR), token("second", HOUR)]);
+        let auth = GcpAuth::from_source(source, "proj").await.unwrap();
+
+        tokio::time::sleep(HOUR - REFRESH_MARGIN - Duration::from_secs(1)).await;
+        assert_eq!(intercept(&auth).metadata().get("authorization").unwrap(), "Bearer first");
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert_eq!(intercept(&auth).metadata().get("authorization").unwrap(), "Bearer second");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn failed_refresh_keeps_previous_token_and_retries() {
+        let source = FakeTokenSource::new(vec![
+            token("first", HOUR),
+            Err(TelemetryError::Auth("transient".to_string())),
+            token("second", HOUR),
+        ]);
+        let auth = GcpAuth::from_source(source, "proj").await.unwrap();
+
+        tokio::time::sleep(HOUR - REFRESH_MARGIN + Duration::from_secs(1)).await;
+        assert_eq!(intercept(&auth).metadata().get("authorization").unwrap(), "Bearer first");
+
+        tokio::time::sleep(RETRY_INTERVAL).await;
+        assert_eq!(intercept(&auth).metadata().get("authorization").unwrap(), "Bearer second");
     }
 }
@@ -1,13 +1,24 @@
 use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::telemetry::config::check_endpoint;
+use crate::telemetry::error::ConfigProblem;
+use crate::telemetry::gcp::metadata::MetadataClient;
 
 /// Default GCP telemetry endpoint
 pub const DEFAULT_ENDPOINT: &str = "https://telemetry.googleapis.com";
 
 /// GCP cloud platforms (maps to cloud.platform semconv values)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum GcpPlatform {
     #[default]
     CloudRun,
+    /// Cloud Run Jobs (batch tasks rather than a request-serving service)
+    CloudRunJob,
     CloudFunctions,
     AppEngine,
     ComputeEngine,
@@ -18,7 +29,8 @@ impl GcpPlatform {
     /// Returns the OpenTelemetry semantic convention value
     pub fn as_str(&self) -> &'static str {
         match self {
-            Self::CloudRun => "gcp_cloud_run",
+            // Semconv has no separate value for jobs; job attributes tell them apart
+            Self::CloudRun | Self::CloudRunJob => "gcp_cloud_run",
             Self::CloudFunctions => "gcp_cloud_functions",
             Self::AppEngine => "gcp_app_engine",
             Self::ComputeEngine => "gcp_compute_engine",
@@ -26,10 +38,15 @@ impl GcpPlatform {
         }
     }
 
-    /// Detect platform from environment variables
+    /// Detect serverless platforms from environment variables.
+    ///
+    /// Compute Engine and GKE set no such variables; see [`PlatformDetector`]
+    /// and [`PlatformDetector::detect_on_gcp`].
     pub fn detect() -> Option<Self> {
         if env::var("K_SERVICE").is_ok() || env::var("K_REVISION").is_ok() {
             Some(Self::CloudRun)
+        } else if env::var("CLOUD_RUN_JOB").is_ok() {
+            Some(Self::CloudRunJob)
         } else if env::var("FUNCTION_NAME").is_ok() || env::var("FUNCTION_TARGET").is_ok() {
             Some(Self::CloudFunctions)
         } else if env::var("GAE_SERVICE").is_ok() || env::var("GAE_VERSION").is_ok() {
@@ -40,14 +57,117 @@ impl GcpPlatform {
     }
 }
 
+/// Root of the sysfs mount holding the DMI identity
+pub const DEFAULT_SYSFS_ROOT: &str = "/sys";
+
+/// DMI product names of Google Compute Engine machines
+const GCE_PRODUCT_NAMES: &[&str] = &["Google Compute Engine", "Google"];
+
+/// Platform detection covering VMs and GKE as well as serverless platforms.
+///
+/// Serverless platforms are recognised by their environment variables. Otherwise
+/// the machine is on Compute Engine if its DMI product name says so or the
+/// metadata server answers, and on GKE if `KUBERNETES_SERVICE_HOST` is also set.
+#[derive(Debug, Clone)]
+pub struct PlatformDetector {
+    sysfs_root: PathBuf,
+    metadata: Option<MetadataClient>,
+}
+
+impl Default for PlatformDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlatformDetector {
+    pub fn new() -> Self {
+        Self {
+            sysfs_root: PathBuf::from(DEFAULT_SYSFS_ROOT),
+            metadata: None,
+        }
+    }
+
+    /// Read DMI from `{root}/class/dmi/id` instead of `/sys/class/dmi/id`
+    pub fn with_sysfs_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.sysfs_root = root.into();
+        self
+    }
+
+    /// Also ask the metadata server whether this is a GCE instance
+    pub fn with_metadata(mut self, metadata: MetadataClient) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    pub async fn detect(&self) -> Option<GcpPlatform> {
+        match GcpPlatform::detect() {
+            Some(platform) => Some(platform),
+            None => self.detect_compute().await,
+        }
+    }
+
+    /// Platform of a service already known to run on GCP (its project is
+    /// configured), without asking the metadata server: serverless variables
+    /// first, then GKE if `KUBERNETES_SERVICE_HOST` is set, then Compute Engine
+    /// if the DMI product name says so
+    pub fn detect_on_gcp(&self) -> Option<GcpPlatform> {
+        GcpPlatform::detect().or_else(|| {
+            if env::var_os("KUBERNETES_SERVICE_HOST").is_some() {
+                Some(GcpPlatform::KubernetesEngine)
+            } else if has_gce_product_name(&self.sysfs_root) {
+                Some(GcpPlatform::ComputeEngine)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Compute Engine or GKE, if this machine is a GCE instance
+    async fn detect_compute(&self) -> Option<GcpPlatform> {
+        if !self.is_gce().await {
+            return None;
+        }
+
+        if env::var_os("KUBERNETES_SERVICE_HOST").is_some() {
+            Some(GcpPlatform::KubernetesEngine)
+        } else {
+            Some(GcpPlatform::ComputeEngine)
+        }
+    }
+
+    async fn is_gce(&self) -> bool {
+        if has_gce_product_name(&self.sysfs_root) {
+            return true;
+        }
+
+        match &self.metadata {
+            Some(metadata) => metadata.instance_id().await.is_some(),
+            None => false,
+        }
+    }
+}
+
+fn has_gce_product_name(sysfs_root: &Path) -> bool {
+    fs::read_to_string(sysfs_root.join("class/dmi/id/product_name"))
+        .is_ok_and(|name| GCE_PRODUCT_NAMES.contains(&name.trim()))
+}
+
 /// GCP-specific configuration
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct GcpConfig {
     pub project_id: String,
+    #[serde(default = "default_endpoint")]
     pub endpoint: String,
+    #[serde(default)]
     pub platform: GcpPlatform,
 }
 
+fn default_endpoint() -> String {
+    DEFAULT_ENDPOINT.to_string()
+}
+
 impl GcpConfig {
     pub fn new(project_id: impl Into<String>) -> Self {
         Self {
@@ -67,50 +187,120 @@ impl GcpConfig {
         self
     }
 
+    /// Record problems with the project ID and endpoint
+    pub fn validate(&self, problems: &mut Vec<ConfigProblem>) {
+        if !is_valid_project_id(&self.project_id) {
+            problems.push(ConfigProblem::new(
+                "gcp.project_id",
+                format!(
+                    "{:?} is not a project ID (6-30 lowercase letters, digits or hyphens, \
+                     starting with a letter)",
+                    self.project_id
+                ),
+            ));
+        }
+        check_endpoint("gcp.endpoint", &self.endpoint, problems);
+    }
+
     /// Create from environment variables
     /// - GOOGLE_CLOUD_PROJECT / GCLOUD_PROJECT / GCP_PROJECT for project_id
     /// - OTEL_EXPORTER_OTLP_ENDPOINT for endpoint (defaults to DEFAULT_ENDPOINT)
-    /// - Platform auto-detected from K_SERVICE, FUNCTION_NAME, GAE_SERVICE, etc.
+    /// - Platform from K_SERVICE, FUNCTION_NAME, GAE_SERVICE, etc., else
+    ///   `KUBERNETES_SERVICE_HOST` or the DMI product name (see
+    ///   [`PlatformDetector::detect_on_gcp`]), else Cloud Run
     pub fn from_env() -> Option<Self> {
-        let project_id = env::var("GOOGLE_CLOUD_PROJECT")
-            .or_else(|_| env::var("GCLOUD_PROJECT"))
-            .or_else(|_| env::var("GCP_PROJECT"))
-            .ok()?;
-
-        let endpoint = env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
-            .unwrap_or_else(|_| DEFAULT_ENDPOINT.to_string());
+        let project_id = project_id_from_env()?;
+        let platform = PlatformDetector::new().detect_on_gcp().unwrap_or_default();
+        Some(Self::new(project_id).with_platform(platform).with_env_overrides())
+    }
 
-        let platform = GcpPlatform::detect().unwrap_or_default();
+    /// Replace the project, endpoint and platform with any the environment sets
+    pub fn with_env_overrides(mut self) -> Self {
+        if let Some(project_id) = project_id_from_env() {
+            self.project_id = project_id;
+        }
+        if let Ok(endpoint) = env::var("OTEL_EXPORTER_OTLP_ENDPOINT") {
+            self.endpoint = endpoint;
+        }
+        if let Some(platform) = GcpPlatform::detect() {
+            self.platform = platform;
+        }
+        self
+    }
 
-        Some(Self {
-            project_id,
-            endpoint,
-            platform,
-        })
+    /// Like [`from_env`](Self::from_env), but falls back to the metadata
+    /// server for the project ID when no project variable is set, and also
+    /// recognises Compute Engine and GKE (see [`PlatformDetector`])
+    pub async fn detect(metadata: &MetadataClient) -> Option<Self> {
+        let mut config = match Self::from_env() {
+            Some(config) => config,
+            None => Self {
+                project_id: metadata.project_id().await?,
+                endpoint: env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+                    .unwrap_or_else(|_| DEFAULT_ENDPOINT.to_string()),
+                platform: GcpPlatform::default(),
+            },
+        };
+
+        // The project is known, so this is GCP; only a bare VM needs the metadata server
+        let detector = PlatformDetector::new().with_metadata(metadata.clone());
+        config.platform = match detector.detect_on_gcp() {
+            Some(platform) => platform,
+            None => detector.detect().await.unwrap_or_default(),
+        };
+        Some(config)
     }
 }
 
+fn project_id_from_env() -> Option<String> {
+    env::var("GOOGLE_CLOUD_PROJECT")
+        .or_else(|_| env::var("GCLOUD_PROJECT"))
+        .or_else(|_| env::var("GCP_PROJECT"))
+        .ok()
+}
+
+/// Project ID format, allowing the legacy `example.com:project` domain prefix
+fn is_valid_project_id(project_id: &str) -> bool {
+    let id = project_id
+        .rsplit_once(':')
+        .map_or(project_id, |(_, id)| id);
+
+    (6..=30).contains(&id.len())
+        && id.starts_with(|c: char| c.is_ascii_lowercase())
+        && !id.ends_with('-')
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::telemetry::testing::EnvGuard;
 
-    // Helper to clean up env vars after tests
-    struct EnvGuard {
-        vars: Vec<&'static str>,
-    }
-
-    impl EnvGuard {
-        fn new(vars: &[&'static str]) -> Self {
-            Self { vars: vars.to_vec() }
+    #[test]
+    fn validates_project_id_format() {
+        for id in ["my-project", "abc123", "example.com:my-project"] {
+            assert!(is_valid_project_id(id), "{}", id);
+        }
+        for id in ["", "short", "My-Project", "1project", "project-", "a_project"] {
+            assert!(!is_valid_project_id(id), "{}", id);
         }
     }
 
-    impl Drop for EnvGuard {
-        fn drop(&mut self) {
-            for var in &self.vars {
-                env::remove_var(var);
-            }
-        }
+    #[test]
+    fn validate_reports_project_and_endpoint() {
+        let mut problems = Vec::new();
+        GcpConfig::new("Bad Project")
+            .with_endpoint("telemetry.googleapis.com")
+            .validate(&mut problems);
+
+        let fields: Vec<_> = problems.iter().map(|problem| problem.field).collect();
+        assert_eq!(fields, vec!["gcp.project_id", "gcp.endpoint"]);
+
+        let mut problems = Vec::new();
+        GcpConfig::new("my-project").validate(&mut problems);
+        assert!(problems.is_empty());
     }
 
     #[test]
@@ -121,6 +311,7 @@ mod tests {
     #[test]
     fn gcp_platform_as_str_returns_semconv_values() {
         assert_eq!(GcpPlatform::CloudRun.as_str(), "gcp_cloud_run");
+        assert_eq!(GcpPlatform::CloudRunJob.as_str(), "gcp_cloud_run");
         assert_eq!(GcpPlatform::CloudFunctions.as_str(), "gcp_cloud_functions");
         assert_eq!(GcpPlatform::AppEngine.as_str(), "gcp_app_engine");
         assert_eq!(GcpPlatform::ComputeEngine.as_str(), "gcp_compute_engine");
@@ -179,6 +370,14 @@ mod tests {
         assert_eq!(GcpPlatform::detect(), Some(GcpPlatform::CloudRun));
     }
 
+    #[test]
+    fn gcp_platform_detect_cloud_run_job() {
+        let _guard = EnvGuard::new(&["CLOUD_RUN_JOB"]);
+        env::set_var("CLOUD_RUN_JOB", "nightly-export");
+
+        assert_eq!(GcpPlatform::detect(), Some(GcpPlatform::CloudRunJob));
+    }
+
     #[test]
     fn gcp_platform_detect_cloud_functions() {
         let _guard = EnvGuard::new(&["FUNCTION_NAME"]);
@@ -198,7 +397,13 @@ mod tests {
     #[test]
     fn gcp_platform_detect_none_when_no_env() {
         // Ensure no GCP env vars are set
-        let _guard = EnvGuard::new(&["K_SERVICE", "K_REVISION", "FUNCTION_NAME", "GAE_SERVICE"]);
+        let _guard = EnvGuard::new(&[
+            "K_SERVICE",
+            "K_REVISION",
+            "CLOUD_RUN_JOB",
+            "FUNCTION_NAME",
+            "GAE_SERVICE",
+        ]);
 
         assert_eq!(GcpPlatform::detect(), None);
     }
@@ -220,6 +425,88 @@ mod tests {
         assert_eq!(config.endpoint, DEFAULT_ENDPOINT);
     }
 
+    #[test]
+    fn gcp_config_from_env_detects_gke() {
+        let _guard = EnvGuard::new(&[
+            "GOOGLE_CLOUD_PROJECT",
+            "KUBERNETES_SERVICE_HOST",
+            "K_SERVICE",
+            "CLOUD_RUN_JOB",
+            "FUNCTION_NAME",
+            "GAE_SERVICE",
+        ]);
+        env::set_var("GOOGLE_CLOUD_PROJECT", "test-project");
+        env::set_var("KUBERNETES_SERVICE_HOST", "10.0.0.1");
+
+        let config = GcpConfig::from_env().unwrap();
+
+        assert_eq!(config.platform, GcpPlatform::KubernetesEngine);
+    }
+
+    #[test]
+    fn platform_detector_on_gcp_reads_dmi_product_name() {
+        let _guard = EnvGuard::new(&["KUBERNETES_SERVICE_HOST", "K_SERVICE"]);
+        let sysfs = tempfile::tempdir().unwrap();
+        let detector = PlatformDetector::new().with_sysfs_root(sysfs.path());
+        assert_eq!(detector.detect_on_gcp(), None);
+
+        let dmi = sysfs.path().join("class/dmi/id");
+        fs::create_dir_all(&dmi).unwrap();
+        fs::write(dmi.join("product_name"), "Google Compute Engine\n").unwrap();
+
+        assert_eq!(detector.detect_on_gcp(), Some(GcpPlatform::ComputeEngine));
+    }
+
+    #[tokio::test]
+    async fn gcp_config_detect_falls_back_to_metadata_server() {
+        use crate::telemetry::gcp::metadata::tests::fake_metadata_server;
+
+        let _guard = EnvGuard::new(&["GOOGLE_CLOUD_PROJECT", "GCLOUD_PROJECT", "GCP_PROJECT"]);
+        let server = fake_metadata_server(&[("project/project-id", "metadata-project")]).await;
+        let metadata = MetadataClient::new(server.uri()).unwrap();
+
+        let config = GcpConfig::detect(&metadata).await.unwrap();
+
+        assert_eq!(config.project_id, "metadata-project");
+    }
+
+    #[tokio::test]
+    async fn platform_detector_reads_dmi_product_name() {
+        let _guard = EnvGuard::new(&["KUBERNETES_SERVICE_HOST"]);
+        let sysfs = tempfile::tempdir().unwrap();
+        let dmi = sysfs.path().join("class/dmi/id");
+        fs::create_dir_all(&dmi).unwrap();
+        fs::write(dmi.join("product_name"), "Google Compute Engine\n").unwrap();
+        let detector = PlatformDetector::new().with_sysfs_root(sysfs.path());
+
+        assert_eq!(
+            detector.detect_compute().await,
+            Some(GcpPlatform::ComputeEngine)
+        );
+
+        env::set_var("KUBERNETES_SERVICE_HOST", "10.0.0.1");
+        assert_eq!(
+            detector.detect_compute().await,
+            Some(GcpPlatform::KubernetesEngine)
+        );
+    }
+
+    #[tokio::test]
+    async fn platform_detector_falls_back_to_metadata_server() {
+        use crate::telemetry::gcp::metadata::tests::fake_metadata_server;
+
+        let sysfs = tempfile::tempdir().unwrap();
+        let server = fake_metadata_server(&[("instance/id", "4520031799277581759")]).await;
+
+        let without_metadata = PlatformDetector::new().with_sysfs_root(sysfs.path());
+        let with_metadata = without_metadata
+            .clone()
+            .with_metadata(MetadataClient::new(server.uri()).unwrap());
+
+        assert_eq!(without_metadata.detect_compute().await, None);
+        assert!(with_metadata.detect_compute().await.is_some());
+    }
+
     #[test]
     fn gcp_config_from_env_with_custom_endpoint() {
         let _guard = EnvGuard::new(&["GOOGLE_CLOUD_PROJECT", "OTEL_EXPORTER_OTLP_ENDPOINT"]);
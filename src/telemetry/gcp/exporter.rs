@@ -1,24 +1,102 @@
-use opentelemetry_otlp::{SpanExporter, WithExportConfig, WithTonicConfig};
+use opentelemetry_otlp::{
+    LogExporter, MetricExporter, SpanExporter, WithExportConfig, WithTonicConfig,
+};
 
 use crate::telemetry::error::TelemetryError;
 use crate::telemetry::gcp::auth::GcpAuth;
+use crate::telemetry::otlp::{OtlpExportConfig, OtlpTlsConfig};
 
 /// Build OTLP exporter configured for GCP Cloud Trace
+///
+/// The bearer token is injected per request by [`GcpAuth`], which refreshes it
+/// in the background for as long as the exporter lives. Headers, timeout and
+/// compression come from `OTEL_EXPORTER_OTLP_TRACES_*`, then `OTEL_EXPORTER_OTLP_*`;
+/// `OTEL_EXPORTER_OTLP_CERTIFICATE` is trusted alongside the platform roots.
 pub async fn build_gcp_exporter(
     project_id: &str,
     endpoint: &str,
 ) -> Result<SpanExporter, TelemetryError> {
     let auth = GcpAuth::from_adc(project_id).await?;
+    let export = OtlpExportConfig::traces_from_env().or(&OtlpExportConfig::from_env());
+    build_span_exporter(auth, endpoint, &export, &OtlpTlsConfig::from_env())
+}
 
-    let tls_config = tonic::transport::ClientTlsConfig::new().with_native_roots();
+/// Build OTLP metric exporter configured for GCP Cloud Monitoring
+pub async fn build_gcp_metric_exporter(
+    project_id: &str,
+    endpoint: &str,
+) -> Result<MetricExporter, TelemetryError> {
+    let auth = GcpAuth::from_adc(project_id).await?;
+    build_metric_exporter(
+        auth,
+        endpoint,
+        &OtlpExportConfig::from_env(),
+        &OtlpTlsConfig::from_env(),
+    )
+}
 
-    let exporter = SpanExporter::builder()
+pub(crate) fn build_span_exporter(
+    auth: GcpAuth,
+    endpoint: &str,
+    export: &OtlpExportConfig,
+    tls: &OtlpTlsConfig,
+) -> Result<SpanExporter, TelemetryError> {
+    let builder = SpanExporter::builder()
         .with_tonic()
         .with_endpoint(endpoint)
-        .with_metadata(auth.metadata)
-        .with_tls_config(tls_config)
+        .with_interceptor(auth);
+
+    configure(builder, export, tls)?
         .build()
-        .map_err(|e| TelemetryError::Exporter(e.to_string()))?;
+        .map_err(|e| TelemetryError::Exporter(e.to_string()))
+}
+
+pub(crate) fn build_metric_exporter(
+    auth: GcpAuth,
+    endpoint: &str,
+    export: &OtlpExportConfig,
+    tls: &OtlpTlsConfig,
+) -> Result<MetricExporter, TelemetryError> {
+    let builder = MetricExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .with_interceptor(auth);
+
+    configure(builder, export, tls)?
+        .build()
+        .map_err(|e| TelemetryError::Exporter(e.to_string()))
+}
+
+pub(crate) fn build_log_exporter(
+    auth: GcpAuth,
+    endpoint: &str,
+    export: &OtlpExportConfig,
+    tls: &OtlpTlsConfig,
+) -> Result<LogExporter, TelemetryError> {
+    let builder = LogExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .with_interceptor(auth);
+
+    configure(builder, export, tls)?
+        .build()
+        .map_err(|e| TelemetryError::Exporter(e.to_string()))
+}
 
-    Ok(exporter)
+/// Apply export settings and TLS with the platform roots plus `otlp_tls`'s CA.
+///
+/// The rest of `otlp_tls` describes the local collector, so its client identity,
+/// domain override and `insecure` flag are not applied here: the bearer token must
+/// not go out in plaintext, and the server is verified as the endpoint's Google host.
+fn configure<B>(
+    builder: B,
+    export: &OtlpExportConfig,
+    tls: &OtlpTlsConfig,
+) -> Result<B, TelemetryError>
+where
+    B: WithExportConfig + WithTonicConfig,
+{
+    Ok(export
+        .apply_tonic(builder)
+        .with_tls_config(tls.trusted_roots()?))
 }
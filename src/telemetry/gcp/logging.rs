@@ -0,0 +1,287 @@
+use std::fmt;
+
+use opentelemetry::trace::TraceContextExt;
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::{FormatTime, SystemTime};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Cloud Logging special fields
+/// (https://cloud.google.com/logging/docs/structured-logging#special-payload-fields)
+pub const TRACE_FIELD: &str = "logging.googleapis.com/trace";
+pub const SPAN_ID_FIELD: &str = "logging.googleapis.com/spanId";
+pub const TRACE_SAMPLED_FIELD: &str = "logging.googleapis.com/trace_sampled";
+pub const SOURCE_LOCATION_FIELD: &str = "logging.googleapis.com/sourceLocation";
+
+/// Event formatter producing Cloud Logging structured JSON.
+///
+/// Writes `severity` and `message` in place of tracing's `level` and
+/// `fields.message`, and links each entry to the active Cloud Trace span.
+#[derive(Debug, Clone, Default)]
+pub struct GcpJsonFormat {
+    project_id: Option<String>,
+}
+
+impl GcpJsonFormat {
+    /// Without a project ID, entries carry no trace link (Cloud Logging needs
+    /// the `projects/{project_id}/traces/{trace_id}` form).
+    pub fn new(project_id: Option<String>) -> Self {
+        Self { project_id }
+    }
+
+    fn entry(&self, event: &Event<'_>) -> Map<String, Value> {
+        let metadata = event.metadata();
+        let mut entry = Map::new();
+
+        let mut time = String::new();
+        if SystemTime.format_time(&mut Writer::new(&mut time)).is_ok() {
+            entry.insert("time".into(), time.into());
+        }
+        entry.insert("severity".into(), severity(metadata.level()).into());
+
+        let mut visitor = JsonVisitor::default();
+        event.record(&mut visitor);
+        entry.insert(
+            "message".into(),
+            visitor.message.take().unwrap_or_default().into(),
+        );
+        entry.insert("target".into(), metadata.target().into());
+        entry.extend(visitor.fields);
+
+        let mut location = Map::new();
+        if let Some(file) = metadata.file() {
+            location.insert("file".into(), file.into());
+        }
+        if let Some(line) = metadata.line() {
+            // The LogEntry API encodes int64 as a JSON string
+            location.insert("line".into(), line.to_string().into());
+        }
+        if let Some(module) = metadata.module_path() {
+            location.insert("function".into(), module.into());
+        }
+        if !location.is_empty() {
+            entry.insert(SOURCE_LOCATION_FIELD.into(), location.into());
+        }
+
+        // The OpenTelemetry layer attaches each entered span's context, and the
+        // dispatcher is unavailable from inside an event callback, so read it here
+        let context = opentelemetry::Context::current();
+        let span = context.span();
+        let span_context = span.span_context();
+        if span_context.is_valid() {
+            if let Some(project_id) = &self.project_id {
+                entry.insert(
+                    TRACE_FIELD.into(),
+                    format!("projects/{}/traces/{}", project_id, span_context.trace_id()).into(),
+                );
+            }
+            entry.insert(
+                SPAN_ID_FIELD.into(),
+                span_context.span_id().to_string().into(),
+            );
+            entry.insert(TRACE_SAMPLED_FIELD.into(), span_context.is_sampled().into());
+        }
+
+        entry
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for GcpJsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        _ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let line = serde_json::to_string(&self.entry(event)).map_err(|_| fmt::Error)?;
+        writeln!(writer, "{}", line)
+    }
+}
+
+/// Build the Cloud Logging JSON fmt layer
+pub fn build_gcp_json_layer<S>(project_id: Option<String>) -> impl Layer<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .event_format(GcpJsonFormat::new(project_id))
+}
+
+/// Map tracing levels onto Cloud Logging `LogSeverity` names
+pub fn severity(level: &Level) -> &'static str {
+    match *level {
+        Level::ERROR => "ERROR",
+        Level::WARN => "WARNING",
+        Level::INFO => "INFO",
+        Level::DEBUG | Level::TRACE => "DEBUG",
+    }
+}
+
+/// Collects event fields as JSON values, keeping `message` apart
+#[derive(Default)]
+struct JsonVisitor {
+    message: Option<String>,
+    fields: Map<String, Value>,
+}
+
+impl JsonVisitor {
+    fn insert(&mut self, field: &Field, value: Value) {
+        if field.name() == "message" {
+            self.message = Some(match value {
+                Value::String(s) => s,
+                other => other.to_string(),
+            });
+        } else {
+            self.fields.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+impl Visit for JsonVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, format!("{:?}", value).into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TracerProvider;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// Collects everything the fmt layer writes
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Buffer {
+        fn lines(&self) -> Vec<Value> {
+            String::from_utf8(self.0.lock().unwrap().clone())
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        }
+    }
+
+    fn capture(project_id: Option<&str>, f: impl FnOnce()) -> Vec<Value> {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let provider = SdkTracerProvider::builder().build();
+
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")))
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(move || writer.clone())
+                    .event_format(GcpJsonFormat::new(project_id.map(String::from))),
+            );
+
+        tracing::subscriber::with_default(subscriber, f);
+        buffer.lines()
+    }
+
+    #[test]
+    fn severity_maps_tracing_levels() {
+        assert_eq!(severity(&Level::ERROR), "ERROR");
+        assert_eq!(severity(&Level::WARN), "WARNING");
+        assert_eq!(severity(&Level::INFO), "INFO");
+        assert_eq!(severity(&Level::DEBUG), "DEBUG");
+        assert_eq!(severity(&Level::TRACE), "DEBUG");
+    }
+
+    #[test]
+    fn writes_severity_message_and_fields() {
+        let lines = capture(Some("my-project"), || {
+            tracing::warn!(user = "bob", attempts = 3, "Login failed");
+        });
+
+        let entry = &lines[0];
+        assert_eq!(entry["severity"], "WARNING");
+        assert_eq!(entry["message"], "Login failed");
+        assert_eq!(entry["user"], "bob");
+        assert_eq!(entry["attempts"], 3);
+        assert!(entry.get("level").is_none());
+        assert!(entry[SOURCE_LOCATION_FIELD]["file"].is_string());
+        assert!(entry[SOURCE_LOCATION_FIELD]["line"].is_string());
+    }
+
+    #[test]
+    fn links_entry_to_active_trace() {
+        let lines = capture(Some("my-project"), || {
+            let span = tracing::info_span!("request");
+            let _entered = span.enter();
+            tracing::info!("inside");
+        });
+
+        let entry = &lines[0];
+        let trace = entry[TRACE_FIELD].as_str().unwrap();
+        let trace_id = trace.strip_prefix("projects/my-project/traces/").unwrap();
+        assert_eq!(trace_id.len(), 32);
+        assert_eq!(entry[SPAN_ID_FIELD].as_str().unwrap().len(), 16);
+        assert_eq!(entry[TRACE_SAMPLED_FIELD], true);
+    }
+
+    #[test]
+    fn omits_trace_outside_spans() {
+        let lines = capture(Some("my-project"), || tracing::info!("outside"));
+
+        assert!(lines[0].get(TRACE_FIELD).is_none());
+        assert!(lines[0].get(SPAN_ID_FIELD).is_none());
+    }
+
+    #[test]
+    fn omits_trace_without_project() {
+        let lines = capture(None, || {
+            let span = tracing::info_span!("request");
+            let _entered = span.enter();
+            tracing::info!("inside");
+        });
+
+        assert!(lines[0].get(TRACE_FIELD).is_none());
+        assert!(lines[0].get(SPAN_ID_FIELD).is_some());
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::telemetry::error::TelemetryError;
+
+/// Metadata server host on GCP
+pub const DEFAULT_METADATA_HOST: &str = "metadata.google.internal";
+
+/// Header the metadata server requires on requests and sets on responses
+const METADATA_FLAVOR: (&str, &str) = ("Metadata-Flavor", "Google");
+
+/// Upper bound for one lookup; off GCP the server never answers, so keep it short
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Client for the GCP metadata server.
+///
+/// Lookups never fail: anything other than a valid answer (no server, an
+/// error status, a response without `Metadata-Flavor: Google`) reads as
+/// `None`. Results are cached, including misses, and once the server proves
+/// unreachable every further lookup returns `None` without a request.
+/// Clones share the cache.
+#[derive(Debug, Clone)]
+pub struct MetadataClient {
+    base_url: String,
+    client: reqwest::Client,
+    cache: Arc<Mutex<Cache>>,
+}
+
+#[derive(Debug, Default)]
+struct Cache {
+    values: HashMap<String, Option<String>>,
+    unreachable: bool,
+}
+
+impl MetadataClient {
+    /// Client for `base_url` (e.g. `http://metadata.google.internal`)
+    pub fn new(base_url: impl Into<String>) -> Result<Self, TelemetryError> {
+        Self::with_timeout(base_url, DEFAULT_TIMEOUT)
+    }
+
+    pub fn with_timeout(
+        base_url: impl Into<String>,
+        timeout: Duration,
+    ) -> Result<Self, TelemetryError> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .connect_timeout(CONNECT_TIMEOUT.min(timeout))
+            .build()
+            .map_err(|e| TelemetryError::Init(format!("Failed to build metadata client: {}", e)))?;
+
+        Ok(Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client,
+            cache: Arc::new(Mutex::new(Cache::default())),
+        })
+    }
+
+    /// Client for `GCE_METADATA_HOST` (`host[:port]`), or the default host
+    pub fn from_env() -> Result<Self, TelemetryError> {
+        let host = env::var("GCE_METADATA_HOST")
+            .ok()
+            .filter(|host| !host.is_empty())
+            .unwrap_or_else(|| DEFAULT_METADATA_HOST.to_string());
+
+        Self::new(format!("http://{}", host))
+    }
+
+    /// Project ID (`project/project-id`)
+    pub async fn project_id(&self) -> Option<String> {
+        self.get("project/project-id").await
+    }
+
+    /// Region, e.g. `us-central1`.
+    ///
+    /// Serverless platforms publish `instance/region`; elsewhere it is derived from the zone.
+    pub async fn region(&self) -> Option<String> {
+        match self.get("instance/region").await {
+            Some(region) => Some(last_segment(&region)),
+            None => self.zone().await.and_then(|zone| region_of_zone(&zone)),
+        }
+    }
+
+    /// Zone, e.g. `us-central1-a` (`instance/zone`)
+    pub async fn zone(&self) -> Option<String> {
+        self.get("instance/zone")
+            .await
+            .map(|zone| last_segment(&zone))
+    }
+
+    /// Instance ID (`instance/id`)
+    pub async fn instance_id(&self) -> Option<String> {
+        self.get("instance/id").await
+    }
+
+    /// Custom instance attribute, e.g. `cluster-name` on GKE nodes
+    pub async fn instance_attribute(&self, name: &str) -> Option<String> {
+        self.get(&format!("instance/attributes/{}", name)).await
+    }
+
+    /// Read `computeMetadata/v1/{path}`, from the cache when possible
+    pub async fn get(&self, path: &str) -> Option<String> {
+        {
+            let cache = self.cache.lock().unwrap();
+            if cache.unreachable {
+                return None;
+            }
+            if let Some(value) = cache.values.get(path) {
+                return value.clone();
+            }
+        }
+
+        let value = match self.fetch(path).await {
+            Ok(value) => value,
+            Err(e) => {
+                tracing::debug!(error = %e, "GCP metadata server unreachable");
+                self.cache.lock().unwrap().unreachable = true;
+                return None;
+            }
+        };
+
+        self.cache
+            .lock()
+            .unwrap()
+            .values
+            .insert(path.to_string(), value.clone());
+        value
+    }
+
+    /// `Ok(None)` for a miss, `Err` when the server can't be reached at all
+    async fn fetch(&self, path: &str) -> Result<Option<String>, reqwest::Error> {
+        let url = format!("{}/computeMetadata/v1/{}", self.base_url, path);
+        let response = self
+            .client
+            .get(url)
+            .header(METADATA_FLAVOR.0, METADATA_FLAVOR.1)
+            .send()
+            .await?;
+
+        let from_metadata_server = response
+            .headers()
+            .get(METADATA_FLAVOR.0)
+            .is_some_and(|value| value == METADATA_FLAVOR.1);
+        if !response.status().is_success() || !from_metadata_server {
+            return Ok(None);
+        }
+
+        let value = response.text().await?;
+        let value = value.trim();
+        Ok((!value.is_empty()).then(|| value.to_string()))
+    }
+}
+
+/// `projects/123/zones/us-central1-a` → `us-central1-a`
+fn last_segment(value: &str) -> String {
+    value.rsplit('/').next().unwrap_or(value).to_string()
+}
+
+/// `us-central1-a` → `us-central1`
+fn region_of_zone(zone: &str) -> Option<String> {
+    zone.rsplit_once('-').map(|(region, _)| region.to_string())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Fake metadata server answering `entries` (path relative to `computeMetadata/v1/`)
+    pub(crate) async fn fake_metadata_server(entries: &[(&str, &str)]) -> MockServer {
+        let server = MockServer::start().await;
+        for (entry, value) in entries {
+            Mock::given(method("GET"))
+                .and(path(format!("/computeMetadata/v1/{}", entry)))
+                .and(header(METADATA_FLAVOR.0, METADATA_FLAVOR.1))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header(METADATA_FLAVOR.0, METADATA_FLAVOR.1)
+                        .set_body_string(*value),
+                )
+                .mount(&server)
+                .await;
+        }
+        server
+    }
+
+    #[tokio::test]
+    async fn reads_project_region_zone_and_instance() {
+        let server = fake_metadata_server(&[
+            ("project/project-id", "my-project"),
+            ("instance/region", "projects/123456/regions/europe-west1"),
+            ("instance/zone", "projects/123456/zones/europe-west1-1"),
+            ("instance/id", "00bf4bf02d"),
+        ])
+        .await;
+        let client = MetadataClient::new(server.uri()).unwrap();
+
+        assert_eq!(client.project_id().await.as_deref(), Some("my-project"));
+        assert_eq!(client.region().await.as_deref(), Some("europe-west1"));
+        assert_eq!(client.zone().await.as_deref(), Some("europe-west1-1"));
+        assert_eq!(client.instance_id().await.as_deref(), Some("00bf4bf02d"));
+    }
+
+    #[tokio::test]
+    async fn derives_region_from_zone() {
+        let server =
+            fake_metadata_server(&[("instance/zone", "projects/123456/zones/us-east4-c")]).await;
+        let client = MetadataClient::new(server.uri()).unwrap();
+
+        assert_eq!(client.region().await.as_deref(), Some("us-east4"));
+    }
+
+    #[tokio::test]
+    async fn caches_values_and_misses() {
+        let server = MockServer::start().await;
+        Mock::given(path("/computeMetadata/v1/project/project-id"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(METADATA_FLAVOR.0, METADATA_FLAVOR.1)
+                    .set_body_string("my-project"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(path("/computeMetadata/v1/instance/id"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = MetadataClient::new(server.uri()).unwrap();
+
+        for _ in 0..3 {
+            assert_eq!(
+                client.clone().project_id().await.as_deref(),
+                Some("my-project")
+            );
+            assert_eq!(client.instance_id().await, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn ignores_responses_without_metadata_flavor() {
+        let server = MockServer::start().await;
+        Mock::given(path("/computeMetadata/v1/project/project-id"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("captive-portal"))
+            .mount(&server)
+            .await;
+        let client = MetadataClient::new(server.uri()).unwrap();
+
+        assert_eq!(client.project_id().await, None);
+    }
+
+    #[tokio::test]
+    async fn unreachable_server_is_not_retried() {
+        // Bind then drop a listener so the port refuses connections
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let client = MetadataClient::new(format!("http://127.0.0.1:{}", port)).unwrap();
+
+        assert_eq!(client.project_id().await, None);
+        assert!(client.cache.lock().unwrap().unreachable);
+        assert_eq!(client.region().await, None);
+    }
+
+    #[test]
+    fn from_env_uses_metadata_host_override() {
+        let _guard = crate::telemetry::testing::EnvGuard::new(&["GCE_METADATA_HOST"]);
+        env::set_var("GCE_METADATA_HOST", "127.0.0.1:8099");
+        let client = MetadataClient::from_env().unwrap();
+
+        assert_eq!(client.base_url, "http://127.0.0.1:8099");
+    }
+}
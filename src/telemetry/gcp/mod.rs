@@ -1,13 +1,16 @@
 //! GCP Cloud Trace telemetry provider.
 //!
 //! This module provides OpenTelemetry integration with Google Cloud Trace
-//! using OTLP/gRPC export with automatic GCP authentication.
+//! and Cloud Monitoring using OTLP/gRPC export with automatic GCP authentication.
 //!
 //! # Features
 //!
-//! - Automatic authentication via Application Default Credentials (ADC)
-//! - Support for multiple GCP platforms (Cloud Run, Cloud Functions, App Engine, etc.)
+//! - Automatic authentication via Application Default Credentials (ADC),
+//!   with tokens refreshed in the background before they expire
+//! - Support for multiple GCP platforms (Cloud Run services and jobs, Cloud Functions, App Engine, etc.)
 //! - Semantic conventions for GCP resource attributes
+//! - `X-Cloud-Trace-Context` propagation, so request spans join the front end's trace
+//! - Cloud Logging structured JSON ([`LogFormat::GcpJson`](crate::telemetry::LogFormat)) linked to traces
 //!
 //! # Example
 //!
@@ -29,36 +32,101 @@
 //! # Environment Variables
 //!
 //! - `GOOGLE_CLOUD_PROJECT` / `GCLOUD_PROJECT` / `GCP_PROJECT`: Project ID
-//! - `OTEL_EXPORTER_OTLP_ENDPOINT`: Custom OTLP endpoint
-//! - `K_SERVICE`, `FUNCTION_NAME`, `GAE_SERVICE`: Platform auto-detection
+//! - `OTEL_EXPORTER_OTLP_ENDPOINT`: Custom OTLP endpoint, verified against the platform
+//!   roots plus `OTEL_EXPORTER_OTLP_CERTIFICATE`; the other TLS variables only apply to
+//!   local collectors
+//! - `K_SERVICE`, `CLOUD_RUN_JOB`, `FUNCTION_NAME`, `GAE_SERVICE`: Platform auto-detection
+//! - `CLOUD_RUN_EXECUTION`, `CLOUD_RUN_TASK_{INDEX,ATTEMPT,COUNT}`: Cloud Run job task attributes
+//! - `KUBERNETES_SERVICE_HOST`: GKE, when the machine is a GCE instance (DMI
+//!   product name or metadata server); `POD_NAMESPACE` / `POD_NAME` label the pod
+//! - `GCE_METADATA_HOST`: Metadata server (`host[:port]`), which supplies the
+//!   project ID when no variable sets it, plus region, zone and instance ID
 
 mod auth;
 pub mod config;
 pub mod exporter;
+pub mod logging;
+pub mod metadata;
+pub mod propagator;
 pub mod resource;
 
+use opentelemetry_otlp::SpanExporter;
+use opentelemetry_sdk::logs::SdkLoggerProvider;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
 use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use tokio::sync::OnceCell;
 
 use crate::telemetry::api::TelemetryProvider;
 use crate::telemetry::config::TelemetryConfig;
 use crate::telemetry::error::TelemetryError;
+use crate::telemetry::fanout::with_secondary_backends;
+use crate::telemetry::gcp::auth::GcpAuth;
+use crate::telemetry::gcp::metadata::MetadataClient;
+use crate::telemetry::logs::build_batch_logger_provider;
+use crate::telemetry::metrics::build_periodic_meter_provider;
 
-pub use config::{GcpConfig, GcpPlatform};
-pub use exporter::build_gcp_exporter;
-pub use resource::GcpResourceBuilder;
+pub use config::{GcpConfig, GcpPlatform, PlatformDetector};
+pub use propagator::CloudTraceContextPropagator;
+pub use exporter::{build_gcp_exporter, build_gcp_metric_exporter};
+pub use resource::{GcpResourceBuilder, JobTask};
 
 /// GCP Cloud Trace telemetry provider.
 ///
-/// Exports traces to Google Cloud Trace using OTLP/gRPC with
-/// automatic authentication via Application Default Credentials.
+/// Exports traces to Google Cloud Trace, metrics to Cloud Monitoring and
+/// (if enabled) logs, using OTLP/gRPC with automatic authentication via
+/// Application Default Credentials. All exporters share one refreshing token.
 pub struct GcpProvider {
     config: GcpConfig,
+    auth: OnceCell<GcpAuth>,
+    metadata: Option<MetadataClient>,
 }
 
 impl GcpProvider {
     /// Create a new GCP provider with the given configuration.
+    ///
+    /// Resource attributes missing from the environment are looked up on the
+    /// metadata server named by `GCE_METADATA_HOST`.
     pub fn new(config: GcpConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            auth: OnceCell::new(),
+            metadata: MetadataClient::from_env().ok(),
+        }
+    }
+
+    /// Use this metadata server client (or none) for resource detection
+    pub fn with_metadata_client(mut self, metadata: Option<MetadataClient>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    async fn auth(&self) -> Result<GcpAuth, TelemetryError> {
+        self.auth
+            .get_or_try_init(|| GcpAuth::from_adc(&self.config.project_id))
+            .await
+            .cloned()
+    }
+
+    /// Cloud Trace span exporter
+    pub(crate) async fn span_exporter(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SpanExporter, TelemetryError> {
+        exporter::build_span_exporter(
+            self.auth().await?,
+            &self.config.endpoint,
+            &config.trace_export(),
+            &config.otlp_tls,
+        )
+    }
+
+    pub(crate) async fn resource(&self, config: &TelemetryConfig) -> Resource {
+        let mut builder = GcpResourceBuilder::new(&self.config.project_id, self.config.platform);
+        if let Some(metadata) = &self.metadata {
+            builder = builder.with_metadata(metadata).await;
+        }
+        builder.build(config)
     }
 }
 
@@ -67,17 +135,41 @@ impl TelemetryProvider for GcpProvider {
         &self,
         config: &TelemetryConfig,
     ) -> Result<SdkTracerProvider, TelemetryError> {
-        let exporter =
-            build_gcp_exporter(&self.config.project_id, &self.config.endpoint).await?;
-
-        let resource =
-            GcpResourceBuilder::new(&self.config.project_id, self.config.platform).build(config);
+        let exporter = self.span_exporter(config).await?;
 
-        let provider = SdkTracerProvider::builder()
+        let builder = SdkTracerProvider::builder()
             .with_batch_exporter(exporter)
-            .with_resource(resource)
-            .build();
+            .with_sampler(config.sampler.to_sampler())
+            .with_resource(self.resource(config).await);
+
+        Ok(with_secondary_backends(builder, config).await.build())
+    }
+
+    async fn build_meter_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkMeterProvider, TelemetryError> {
+        let exporter = exporter::build_metric_exporter(
+            self.auth().await?,
+            &self.config.endpoint,
+            &config.otlp_export,
+            &config.otlp_tls,
+        )?;
+
+        Ok(build_periodic_meter_provider(exporter, self.resource(config).await))
+    }
+
+    async fn build_logger_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkLoggerProvider, TelemetryError> {
+        let exporter = exporter::build_log_exporter(
+            self.auth().await?,
+            &self.config.endpoint,
+            &config.otlp_export,
+            &config.otlp_tls,
+        )?;
 
-        Ok(provider)
+        Ok(build_batch_logger_provider(exporter, self.resource(config).await))
     }
 }
@@ -0,0 +1,176 @@
+use std::sync::LazyLock;
+
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::propagation::{text_map_propagator::FieldIter, Extractor, Injector};
+use opentelemetry::trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState};
+use opentelemetry::Context;
+
+/// Header set by the Cloud Run front end, load balancers and other GCP services
+pub const CLOUD_TRACE_CONTEXT_HEADER: &str = "x-cloud-trace-context";
+
+static CLOUD_TRACE_CONTEXT_FIELDS: LazyLock<[String; 1]> =
+    LazyLock::new(|| [CLOUD_TRACE_CONTEXT_HEADER.to_string()]);
+
+/// Propagator for the `X-Cloud-Trace-Context: TRACE_ID/SPAN_ID;o=OPTIONS` format.
+///
+/// `TRACE_ID` is 32 hex characters, `SPAN_ID` is a decimal u64 and `o=1`
+/// marks the trace as sampled; a missing `o` means not sampled.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CloudTraceContextPropagator {
+    _private: (),
+}
+
+impl CloudTraceContextPropagator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TextMapPropagator for CloudTraceContextPropagator {
+    fn inject_context(&self, cx: &Context, injector: &mut dyn Injector) {
+        let span = cx.span();
+        let span_context = span.span_context();
+        if span_context.is_valid() {
+            injector.set(CLOUD_TRACE_CONTEXT_HEADER, format_header(span_context));
+        }
+    }
+
+    fn extract_with_context(&self, cx: &Context, extractor: &dyn Extractor) -> Context {
+        extractor
+            .get(CLOUD_TRACE_CONTEXT_HEADER)
+            .and_then(parse_header)
+            .map(|span_context| cx.with_remote_span_context(span_context))
+            .unwrap_or_else(|| cx.clone())
+    }
+
+    fn fields(&self) -> FieldIter<'_> {
+        FieldIter::new(CLOUD_TRACE_CONTEXT_FIELDS.as_ref())
+    }
+}
+
+/// Render a span context as an `X-Cloud-Trace-Context` value
+pub fn format_header(span_context: &SpanContext) -> String {
+    format!(
+        "{}/{};o={}",
+        span_context.trace_id(),
+        u64::from_be_bytes(span_context.span_id().to_bytes()),
+        u8::from(span_context.is_sampled())
+    )
+}
+
+/// Parse an `X-Cloud-Trace-Context` value into a remote span context
+pub fn parse_header(value: &str) -> Option<SpanContext> {
+    let (ids, options) = match value.trim().split_once(';') {
+        Some((ids, options)) => (ids, Some(options)),
+        None => (value.trim(), None),
+    };
+    let (trace_id, span_id) = ids.split_once('/')?;
+
+    if trace_id.is_empty() || trace_id.len() > 32 {
+        return None;
+    }
+    let trace_id = TraceId::from(u128::from_str_radix(trace_id, 16).ok()?);
+    let span_id = SpanId::from(span_id.parse::<u64>().ok()?);
+
+    let sampled = options
+        .and_then(|options| options.trim().strip_prefix("o="))
+        .is_some_and(|flags| flags.parse::<u8>().is_ok_and(|flags| flags & 1 == 1));
+    let trace_flags = if sampled {
+        TraceFlags::SAMPLED
+    } else {
+        TraceFlags::default()
+    };
+
+    let span_context =
+        SpanContext::new(trace_id, span_id, trace_flags, true, TraceState::default());
+    span_context.is_valid().then_some(span_context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const TRACE_ID: &str = "105445aa7843bc8bf206b12000100000";
+
+    fn extract(headers: &[(&str, &str)]) -> SpanContext {
+        let carrier: HashMap<String, String> = headers
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let cx = CloudTraceContextPropagator::new().extract(&carrier);
+        cx.span().span_context().clone()
+    }
+
+    #[test]
+    fn parses_sampled_header() {
+        let sc = parse_header(&format!("{}/1;o=1", TRACE_ID)).unwrap();
+
+        assert_eq!(sc.trace_id().to_string(), TRACE_ID);
+        assert_eq!(sc.span_id(), SpanId::from(1u64));
+        assert!(sc.is_sampled());
+        assert!(sc.is_remote());
+    }
+
+    #[test]
+    fn parses_unsampled_and_missing_options() {
+        assert!(!parse_header(&format!("{}/1;o=0", TRACE_ID))
+            .unwrap()
+            .is_sampled());
+        assert!(!parse_header(&format!("{}/1", TRACE_ID))
+            .unwrap()
+            .is_sampled());
+    }
+
+    #[test]
+    fn parses_decimal_span_id() {
+        let sc = parse_header(&format!("{}/18446744073709551615;o=1", TRACE_ID)).unwrap();
+
+        assert_eq!(sc.span_id(), SpanId::from(u64::MAX));
+    }
+
+    #[test]
+    fn rejects_malformed_headers() {
+        assert!(parse_header("").is_none());
+        assert!(parse_header(TRACE_ID).is_none());
+        assert!(parse_header(&format!("{}/abc;o=1", TRACE_ID)).is_none());
+        assert!(parse_header("not-hex/1;o=1").is_none());
+        assert!(parse_header(&format!("{}0/1;o=1", TRACE_ID)).is_none());
+        // All-zero IDs are invalid
+        assert!(parse_header(&format!("{}/0;o=1", TRACE_ID)).is_none());
+        assert!(parse_header("00000000000000000000000000000000/1;o=1").is_none());
+    }
+
+    #[test]
+    fn format_round_trips() {
+        let header = format!("{}/12345;o=1", TRACE_ID);
+
+        assert_eq!(format_header(&parse_header(&header).unwrap()), header);
+    }
+
+    #[test]
+    fn extract_reads_header() {
+        let sc = extract(&[(CLOUD_TRACE_CONTEXT_HEADER, &format!("{}/7;o=1", TRACE_ID))]);
+
+        assert_eq!(sc.trace_id().to_string(), TRACE_ID);
+    }
+
+    #[test]
+    fn extract_without_header_is_empty() {
+        assert!(!extract(&[]).is_valid());
+    }
+
+    #[test]
+    fn inject_writes_header() {
+        let sc = parse_header(&format!("{}/42;o=1", TRACE_ID)).unwrap();
+        let cx = Context::new().with_remote_span_context(sc);
+        let mut carrier = HashMap::new();
+
+        CloudTraceContextPropagator::new().inject_context(&cx, &mut carrier);
+
+        assert_eq!(
+            carrier.get(CLOUD_TRACE_CONTEXT_HEADER).unwrap(),
+            &format!("{}/42;o=1", TRACE_ID)
+        );
+    }
+}
@@ -1,11 +1,14 @@
 use opentelemetry::KeyValue;
 use opentelemetry_sdk::Resource;
 use opentelemetry_semantic_conventions::resource::{
-    CLOUD_ACCOUNT_ID, CLOUD_PLATFORM, CLOUD_PROVIDER, CLOUD_REGION, FAAS_NAME, FAAS_VERSION,
+    CLOUD_ACCOUNT_ID, CLOUD_AVAILABILITY_ZONE, CLOUD_PLATFORM, CLOUD_PROVIDER, CLOUD_REGION,
+    FAAS_INSTANCE, FAAS_NAME, FAAS_VERSION, GCP_CLOUD_RUN_JOB_EXECUTION,
+    GCP_CLOUD_RUN_JOB_TASK_INDEX, HOST_ID, K8S_CLUSTER_NAME, K8S_NAMESPACE_NAME, K8S_POD_NAME,
 };
 
 use crate::telemetry::config::TelemetryConfig;
 use crate::telemetry::gcp::config::GcpPlatform;
+use crate::telemetry::gcp::metadata::MetadataClient;
 use crate::telemetry::resource::build_resource;
 
 /// GCP cloud provider value (semconv)
@@ -14,13 +17,51 @@ pub const CLOUD_PROVIDER_GCP: &str = "gcp";
 /// GCP project ID attribute (required by Cloud Trace)
 pub const GCP_PROJECT_ID: &str = "gcp.project_id";
 
+/// Cloud Run job task attempt (no semconv equivalent)
+pub const GCP_CLOUD_RUN_JOB_TASK_ATTEMPT: &str = "gcp.cloud_run.job.task_attempt";
+
+/// Number of tasks in the Cloud Run job execution (no semconv equivalent)
+pub const GCP_CLOUD_RUN_JOB_TASK_COUNT: &str = "gcp.cloud_run.job.task_count";
+
+/// Namespace of the pod's service account, mounted into every GKE pod
+const SERVICE_ACCOUNT_NAMESPACE_FILE: &str = "/var/run/secrets/kubernetes.io/serviceaccount/namespace";
+
 /// GCP-specific resource attributes
 pub struct GcpResourceBuilder {
     project_id: String,
     platform: GcpPlatform,
     region: Option<String>,
+    zone: Option<String>,
+    instance_id: Option<String>,
     service_id: Option<String>,
     revision: Option<String>,
+    cluster_name: Option<String>,
+    namespace: Option<String>,
+    pod_name: Option<String>,
+    job_task: Option<JobTask>,
+}
+
+/// Position of this task within a Cloud Run job execution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobTask {
+    pub execution: String,
+    pub index: u32,
+    pub attempt: u32,
+    pub count: u32,
+}
+
+impl JobTask {
+    /// Read `CLOUD_RUN_EXECUTION` and `CLOUD_RUN_TASK_{INDEX,ATTEMPT,COUNT}`
+    pub fn from_env() -> Option<Self> {
+        let number = |var: &str| std::env::var(var).ok()?.parse().ok();
+
+        Some(Self {
+            execution: std::env::var("CLOUD_RUN_EXECUTION").ok()?,
+            index: number("CLOUD_RUN_TASK_INDEX")?,
+            attempt: number("CLOUD_RUN_TASK_ATTEMPT")?,
+            count: number("CLOUD_RUN_TASK_COUNT")?,
+        })
+    }
 }
 
 impl GcpResourceBuilder {
@@ -32,14 +73,42 @@ impl GcpResourceBuilder {
                 .or_else(|_| std::env::var("FUNCTION_REGION"))
                 .or_else(|_| std::env::var("GAE_REGION"))
                 .ok(),
+            zone: None,
+            instance_id: None,
             service_id: std::env::var("K_SERVICE")
+                .or_else(|_| std::env::var("CLOUD_RUN_JOB"))
                 .or_else(|_| std::env::var("FUNCTION_NAME"))
                 .or_else(|_| std::env::var("GAE_SERVICE"))
                 .ok(),
             revision: std::env::var("K_REVISION")
                 .or_else(|_| std::env::var("GAE_VERSION"))
                 .ok(),
+            cluster_name: None,
+            namespace: None,
+            pod_name: None,
+            job_task: match platform {
+                GcpPlatform::CloudRunJob => JobTask::from_env(),
+                _ => None,
+            },
         }
+        .with_kubernetes_env()
+    }
+
+    /// Pod namespace and name on GKE: `POD_NAMESPACE` / `POD_NAME` (downward
+    /// API), else the service account namespace and the pod hostname
+    fn with_kubernetes_env(mut self) -> Self {
+        if self.platform != GcpPlatform::KubernetesEngine {
+            return self;
+        }
+
+        self.namespace = std::env::var("POD_NAMESPACE")
+            .ok()
+            .or_else(|| std::fs::read_to_string(SERVICE_ACCOUNT_NAMESPACE_FILE).ok())
+            .map(|namespace| namespace.trim().to_string());
+        self.pod_name = std::env::var("POD_NAME")
+            .or_else(|_| std::env::var("HOSTNAME"))
+            .ok();
+        self
     }
 
     pub fn with_region(mut self, region: impl Into<String>) -> Self {
@@ -47,6 +116,55 @@ impl GcpResourceBuilder {
         self
     }
 
+    pub fn with_zone(mut self, zone: impl Into<String>) -> Self {
+        self.zone = Some(zone.into());
+        self
+    }
+
+    pub fn with_instance_id(mut self, instance_id: impl Into<String>) -> Self {
+        self.instance_id = Some(instance_id.into());
+        self
+    }
+
+    /// Job execution and task (Cloud Run Jobs)
+    pub fn with_job_task(mut self, job_task: JobTask) -> Self {
+        self.job_task = Some(job_task);
+        self
+    }
+
+    pub fn with_cluster_name(mut self, cluster_name: impl Into<String>) -> Self {
+        self.cluster_name = Some(cluster_name.into());
+        self
+    }
+
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    pub fn with_pod_name(mut self, pod_name: impl Into<String>) -> Self {
+        self.pod_name = Some(pod_name.into());
+        self
+    }
+
+    /// Fill region, zone, instance ID and (on GKE) cluster name from the
+    /// metadata server where not already set
+    pub async fn with_metadata(mut self, metadata: &MetadataClient) -> Self {
+        if self.region.is_none() {
+            self.region = metadata.region().await;
+        }
+        if self.zone.is_none() {
+            self.zone = metadata.zone().await;
+        }
+        if self.instance_id.is_none() {
+            self.instance_id = metadata.instance_id().await;
+        }
+        if self.platform == GcpPlatform::KubernetesEngine && self.cluster_name.is_none() {
+            self.cluster_name = metadata.instance_attribute("cluster-name").await;
+        }
+        self
+    }
+
     pub fn with_service(mut self, service_id: impl Into<String>) -> Self {
         self.service_id = Some(service_id.into());
         self
@@ -69,6 +187,38 @@ impl GcpResourceBuilder {
             attrs.push(KeyValue::new(CLOUD_REGION, region));
         }
 
+        if let Some(zone) = self.zone {
+            attrs.push(KeyValue::new(CLOUD_AVAILABILITY_ZONE, zone));
+        }
+
+        if let Some(instance_id) = self.instance_id {
+            // VMs are hosts; serverless instances are FaaS instances
+            let key = match self.platform {
+                GcpPlatform::ComputeEngine | GcpPlatform::KubernetesEngine => HOST_ID,
+                _ => FAAS_INSTANCE,
+            };
+            attrs.push(KeyValue::new(key, instance_id));
+        }
+
+        if let Some(task) = self.job_task {
+            attrs.push(KeyValue::new(GCP_CLOUD_RUN_JOB_EXECUTION, task.execution));
+            attrs.push(KeyValue::new(GCP_CLOUD_RUN_JOB_TASK_INDEX, i64::from(task.index)));
+            attrs.push(KeyValue::new(GCP_CLOUD_RUN_JOB_TASK_ATTEMPT, i64::from(task.attempt)));
+            attrs.push(KeyValue::new(GCP_CLOUD_RUN_JOB_TASK_COUNT, i64::from(task.count)));
+        }
+
+        if let Some(cluster_name) = self.cluster_name {
+            attrs.push(KeyValue::new(K8S_CLUSTER_NAME, cluster_name));
+        }
+
+        if let Some(namespace) = self.namespace {
+            attrs.push(KeyValue::new(K8S_NAMESPACE_NAME, namespace));
+        }
+
+        if let Some(pod_name) = self.pod_name {
+            attrs.push(KeyValue::new(K8S_POD_NAME, pod_name));
+        }
+
         if let Some(service_id) = self.service_id {
             attrs.push(KeyValue::new(FAAS_NAME, service_id));
         }
@@ -84,6 +234,7 @@ impl GcpResourceBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::telemetry::testing::EnvGuard;
 
     fn test_config() -> TelemetryConfig {
         TelemetryConfig::new("test-service", "1.0.0")
@@ -122,6 +273,121 @@ mod tests {
         assert!(!resource.is_empty());
     }
 
+    #[tokio::test]
+    async fn gcp_resource_builder_fills_from_metadata() {
+        use crate::telemetry::gcp::metadata::tests::fake_metadata_server;
+        use opentelemetry::Key;
+
+        let server = fake_metadata_server(&[
+            ("instance/region", "projects/123456/regions/us-central1"),
+            ("instance/zone", "projects/123456/zones/us-central1-1"),
+            ("instance/id", "0087244a"),
+        ])
+        .await;
+        let metadata = MetadataClient::new(server.uri()).unwrap();
+
+        let resource = GcpResourceBuilder::new("proj", GcpPlatform::CloudRun)
+            .with_region("europe-west1")
+            .with_metadata(&metadata)
+            .await
+            .build(&test_config());
+
+        // Explicit values win over the metadata server
+        assert_eq!(
+            resource.get(&Key::new(CLOUD_REGION)),
+            Some("europe-west1".into())
+        );
+        assert_eq!(
+            resource.get(&Key::new(CLOUD_AVAILABILITY_ZONE)),
+            Some("us-central1-1".into())
+        );
+        assert_eq!(
+            resource.get(&Key::new(FAAS_INSTANCE)),
+            Some("0087244a".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn gcp_resource_builder_adds_gke_attributes() {
+        use crate::telemetry::gcp::metadata::tests::fake_metadata_server;
+        use opentelemetry::Key;
+
+        let server = fake_metadata_server(&[
+            ("instance/id", "4520031799277581759"),
+            ("instance/attributes/cluster-name", "prod-cluster"),
+        ])
+        .await;
+        let metadata = MetadataClient::new(server.uri()).unwrap();
+
+        let resource = GcpResourceBuilder::new("proj", GcpPlatform::KubernetesEngine)
+            .with_namespace("payments")
+            .with_pod_name("api-7d9f-x2x")
+            .with_metadata(&metadata)
+            .await
+            .build(&test_config());
+
+        let get = |key: &'static str| resource.get(&Key::new(key));
+        assert_eq!(get(K8S_CLUSTER_NAME), Some("prod-cluster".into()));
+        assert_eq!(get(K8S_NAMESPACE_NAME), Some("payments".into()));
+        assert_eq!(get(K8S_POD_NAME), Some("api-7d9f-x2x".into()));
+        assert_eq!(get(HOST_ID), Some("4520031799277581759".into()));
+        assert_eq!(get(FAAS_INSTANCE), None);
+    }
+
+    #[test]
+    fn gcp_resource_builder_adds_job_attributes() {
+        use opentelemetry::Key;
+
+        let resource = GcpResourceBuilder::new("proj", GcpPlatform::CloudRunJob)
+            .with_service("nightly-export")
+            .with_job_task(JobTask {
+                execution: "nightly-export-x7k2p".to_string(),
+                index: 3,
+                attempt: 1,
+                count: 10,
+            })
+            .build(&test_config());
+
+        let get = |key: &'static str| resource.get(&Key::new(key));
+        assert_eq!(get(FAAS_NAME), Some("nightly-export".into()));
+        assert_eq!(
+            get(GCP_CLOUD_RUN_JOB_EXECUTION),
+            Some("nightly-export-x7k2p".into())
+        );
+        assert_eq!(get(GCP_CLOUD_RUN_JOB_TASK_INDEX), Some(3_i64.into()));
+        assert_eq!(get(GCP_CLOUD_RUN_JOB_TASK_ATTEMPT), Some(1_i64.into()));
+        assert_eq!(get(GCP_CLOUD_RUN_JOB_TASK_COUNT), Some(10_i64.into()));
+    }
+
+    #[test]
+    fn job_task_from_env_requires_every_variable() {
+        let _guard = EnvGuard::new(&[
+            "CLOUD_RUN_EXECUTION",
+            "CLOUD_RUN_TASK_INDEX",
+            "CLOUD_RUN_TASK_ATTEMPT",
+            "CLOUD_RUN_TASK_COUNT",
+        ]);
+        std::env::set_var("CLOUD_RUN_EXECUTION", "export-x7k2p");
+        std::env::set_var("CLOUD_RUN_TASK_INDEX", "3");
+        std::env::set_var("CLOUD_RUN_TASK_ATTEMPT", "0");
+        std::env::set_var("CLOUD_RUN_TASK_COUNT", "10");
+
+        let task = JobTask::from_env();
+        std::env::remove_var("CLOUD_RUN_TASK_COUNT");
+        let partial = JobTask::from_env();
+
+        assert_eq!(
+            task,
+            Some(JobTask {
+                execution: "export-x7k2p".to_string(),
+                index: 3,
+                attempt: 0,
+                count: 10,
+            })
+        );
+        assert_eq!(partial, None);
+    }
+
     #[test]
     fn gcp_resource_builder_chain_methods() {
         let config = test_config();
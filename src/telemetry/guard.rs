@@ -0,0 +1,285 @@
+use std::sync::mpsc;
+use std::time::Duration;
+
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::logs::SdkLoggerProvider;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+
+use crate::telemetry::error::TelemetryError;
+use crate::telemetry::log_level::LogLevelHandle;
+
+/// Default upper bound for flushing and shutting down providers
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Owns the installed providers and flushes them on shutdown.
+///
+/// Call [`TelemetryGuard::shutdown`] before the process exits; dropping the
+/// guard does the same work, but blocks the current thread while doing it.
+#[must_use = "dropping the guard immediately shuts telemetry down"]
+pub struct TelemetryGuard {
+    providers: Option<Providers>,
+    log_level: Option<LogLevelHandle>,
+    timeout: Duration,
+}
+
+/// Providers owned by the guard, shut down together
+struct Providers {
+    tracer: SdkTracerProvider,
+    meter: Option<SdkMeterProvider>,
+    logger: Option<SdkLoggerProvider>,
+}
+
+impl TelemetryGuard {
+    pub fn new(tracer_provider: SdkTracerProvider) -> Self {
+        Self {
+            providers: Some(Providers {
+                tracer: tracer_provider,
+                meter: None,
+                logger: None,
+            }),
+            log_level: None,
+            timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+        }
+    }
+
+    pub fn with_meter_provider(mut self, meter_provider: SdkMeterProvider) -> Self {
+        if let Some(providers) = &mut self.providers {
+            providers.meter = Some(meter_provider);
+        }
+        self
+    }
+
+    pub fn with_logger_provider(mut self, logger_provider: SdkLoggerProvider) -> Self {
+        if let Some(providers) = &mut self.providers {
+            providers.logger = Some(logger_provider);
+        }
+        self
+    }
+
+    pub fn with_log_level(mut self, log_level: LogLevelHandle) -> Self {
+        self.log_level = Some(log_level);
+        self
+    }
+
+    /// Set the upper bound for flush + shutdown
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn tracer_provider(&self) -> Option<&SdkTracerProvider> {
+        self.providers.as_ref().map(|p| &p.tracer)
+    }
+
+    pub fn meter_provider(&self) -> Option<&SdkMeterProvider> {
+        self.providers.as_ref().and_then(|p| p.meter.as_ref())
+    }
+
+    pub fn logger_provider(&self) -> Option<&SdkLoggerProvider> {
+        self.providers.as_ref().and_then(|p| p.logger.as_ref())
+    }
+
+    /// Handle to change the log filter at runtime
+    pub fn log_level(&self) -> Option<&LogLevelHandle> {
+        self.log_level.as_ref()
+    }
+
+    /// Flush pending telemetry and shut the providers down
+    pub async fn shutdown(mut self) -> Result<(), TelemetryError> {
+        let Some(providers) = self.providers.take() else {
+            return Ok(());
+        };
+        let timeout = self.timeout;
+
+        tokio::task::spawn_blocking(move || shutdown_providers(providers, timeout))
+            .await
+            .map_err(|e| TelemetryError::Shutdown(e.to_string()))?
+    }
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Some(providers) = self.providers.take() {
+            if let Err(e) = shutdown_providers(providers, self.timeout) {
+                tracing::error!(error = %e, "Failed to flush telemetry on drop");
+            }
+        }
+    }
+}
+
+impl Providers {
+    /// Flush then shut down every provider, keeping the first error
+    fn flush_and_shutdown(&self, timeout: Duration) -> OTelSdkResult {
+        let mut results = vec![
+            self.tracer.force_flush(),
+            self.tracer.shutdown_with_timeout(timeout),
+        ];
+
+        if let Some(meter) = &self.meter {
+            results.push(meter.force_flush());
+            results.push(meter.shutdown_with_timeout(timeout));
+        }
+
+        if let Some(logger) = &self.logger {
+            results.push(logger.force_flush());
+            results.push(logger.shutdown_with_timeout(timeout));
+        }
+
+        results.into_iter().collect()
+    }
+}
+
+/// Flush and shut down on a helper thread, giving up after `timeout`.
+///
+/// A hung exporter must never keep the process alive past Cloud Run's
+/// termination grace period, so the helper thread is abandoned on timeout.
+fn shutdown_providers(providers: Providers, timeout: Duration) -> Result<(), TelemetryError> {
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let _ = tx.send(providers.flush_and_shutdown(timeout));
+    });
+
+    match rx.recv_timeout(timeout) {
+        Ok(result) => result.map_err(|e| TelemetryError::Shutdown(e.to_string())),
+        Err(_) => Err(TelemetryError::Shutdown(format!(
+            "Timed out after {:?} flushing telemetry providers",
+            timeout
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::logs::{LogRecord, Logger, LoggerProvider};
+    use opentelemetry::metrics::MeterProvider;
+    use opentelemetry::trace::{Tracer, TracerProvider};
+    use opentelemetry_sdk::logs::{LogBatch, LogExporter};
+    use opentelemetry_sdk::metrics::{InMemoryMetricExporter, PeriodicReader};
+    use opentelemetry_sdk::trace::{SpanData, SpanExporter};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Counts exported spans and log records
+    #[derive(Debug, Clone, Default)]
+    struct CountingExporter {
+        exported: Arc<AtomicUsize>,
+    }
+
+    impl SpanExporter for CountingExporter {
+        async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+            self.exported.fetch_add(batch.len(), Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    impl LogExporter for CountingExporter {
+        async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
+            self.exported
+                .fetch_add(batch.iter().count(), Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    /// Never finishes an export
+    #[derive(Debug)]
+    struct HangingExporter;
+
+    impl SpanExporter for HangingExporter {
+        async fn export(&self, _batch: Vec<SpanData>) -> OTelSdkResult {
+            std::thread::sleep(Duration::from_secs(60));
+            Ok(())
+        }
+    }
+
+    fn emit_span(provider: &SdkTracerProvider) {
+        provider.tracer("test").in_span("work", |_| {});
+    }
+
+    #[tokio::test]
+    async fn shutdown_flushes_batched_spans() {
+        let exporter = CountingExporter::default();
+        let exported = exporter.exported.clone();
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .build();
+        emit_span(&provider);
+
+        TelemetryGuard::new(provider).shutdown().await.unwrap();
+
+        assert_eq!(exported.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn drop_flushes_batched_spans() {
+        let exporter = CountingExporter::default();
+        let exported = exporter.exported.clone();
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .build();
+        emit_span(&provider);
+
+        drop(TelemetryGuard::new(provider));
+
+        assert_eq!(exported.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn shutdown_flushes_meter_provider() {
+        let exporter = InMemoryMetricExporter::default();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        meter_provider
+            .meter("test")
+            .u64_counter("hits")
+            .build()
+            .add(1, &[]);
+
+        TelemetryGuard::new(SdkTracerProvider::builder().build())
+            .with_meter_provider(meter_provider)
+            .shutdown()
+            .await
+            .unwrap();
+
+        assert!(!exporter.get_finished_metrics().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn shutdown_flushes_logger_provider() {
+        let exporter = CountingExporter::default();
+        let exported = exporter.exported.clone();
+        let logger_provider = SdkLoggerProvider::builder()
+            .with_batch_exporter(exporter)
+            .build();
+        let logger = logger_provider.logger("test");
+        let mut record = logger.create_log_record();
+        record.set_body("hello".into());
+        logger.emit(record);
+
+        TelemetryGuard::new(SdkTracerProvider::builder().build())
+            .with_logger_provider(logger_provider)
+            .shutdown()
+            .await
+            .unwrap();
+
+        assert_eq!(exported.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn shutdown_is_bounded_by_timeout() {
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(HangingExporter)
+            .build();
+        emit_span(&provider);
+
+        let guard = TelemetryGuard::new(provider).with_timeout(Duration::from_millis(100));
+        let started = std::time::Instant::now();
+        let result = guard.shutdown().await;
+
+        assert!(matches!(result, Err(TelemetryError::Shutdown(_))));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
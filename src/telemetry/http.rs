@@ -0,0 +1,147 @@
+//! actix-web tracing helpers.
+//!
+//! `TracingLogger::default()` already continues the caller's trace: its
+//! `root_span!` takes the parent from the global propagator installed by
+//! [`init_propagator`](super::propagation::init_propagator), so an incoming
+//! `traceparent` (or `X-Cloud-Trace-Context`) becomes the parent of the request
+//! span. Incoming baggage is client input and is left to the application.
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{Error, HttpMessage};
+use opentelemetry::trace::{SpanContext, TraceContextExt};
+use tracing_actix_web::RootSpan;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Response header echoing the server span (W3C Trace Context Level 2)
+pub const TRACERESPONSE_HEADER: &str = "traceresponse";
+
+/// Middleware adding a `traceresponse` header with the request's trace ID.
+///
+/// Register it with `middleware::from_fn(trace_response)` *before*
+/// `TracingLogger`, so it runs inside the root span.
+pub async fn trace_response(
+    request: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let root_span = request.extensions().get::<RootSpan>().cloned();
+    let mut response = next.call(request).await?;
+
+    let span_context = root_span.map(|span| span.context().span().span_context().clone());
+    if let Some(span_context) = span_context.filter(SpanContext::is_valid) {
+        if let Ok(value) = HeaderValue::try_from(format_traceresponse(&span_context)) {
+            response
+                .headers_mut()
+                .insert(HeaderName::from_static(TRACERESPONSE_HEADER), value);
+        }
+    }
+
+    Ok(response)
+}
+
+/// Render a span context as `version-trace_id-span_id-flags`
+pub fn format_traceresponse(span_context: &SpanContext) -> String {
+    format!(
+        "00-{}-{}-{:02x}",
+        span_context.trace_id(),
+        span_context.span_id(),
+        span_context.trace_flags().to_u8()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::propagation::init_propagator;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::{middleware, web, App};
+    use opentelemetry::trace::TracerProvider;
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+    use tracing_actix_web::TracingLogger;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn formats_traceresponse() {
+        let span_context = SpanContext::new(
+            u128::from_str_radix(TRACE_ID, 16).unwrap().into(),
+            1u64.into(),
+            opentelemetry::trace::TraceFlags::SAMPLED,
+            false,
+            Default::default(),
+        );
+
+        assert_eq!(
+            format_traceresponse(&span_context),
+            format!("00-{}-0000000000000001-01", TRACE_ID)
+        );
+    }
+
+    #[actix_web::test]
+    async fn continues_incoming_trace_and_echoes_it() {
+        init_propagator();
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let app = init_service(
+            App::new()
+                .wrap(middleware::from_fn(trace_response))
+                .wrap(TracingLogger::default())
+                .route("/", web::get().to(|| async { "ok" })),
+        )
+        .await;
+
+        let req = TestRequest::get()
+            .uri("/")
+            .insert_header(("traceparent", TRACEPARENT))
+            .insert_header(("baggage", "tenant=acme"))
+            .to_request();
+        let response = call_service(&app, req).await;
+
+        let header = response.headers().get(TRACERESPONSE_HEADER).unwrap();
+        let header = header.to_str().unwrap();
+        assert!(header.starts_with(&format!("00-{}-", TRACE_ID)));
+        assert!(!header.contains("00f067aa0ba902b7"));
+
+        drop(response);
+        let spans = exporter.get_finished_spans().unwrap();
+        let root = spans.iter().find(|s| s.parent_span_is_remote).unwrap();
+        assert_eq!(root.span_context.trace_id().to_string(), TRACE_ID);
+        assert_eq!(root.parent_span_id.to_string(), "00f067aa0ba902b7");
+        assert!(root
+            .attributes
+            .iter()
+            .all(|kv| kv.key.as_str() != "tenant"));
+    }
+
+    #[actix_web::test]
+    async fn starts_new_trace_without_traceparent() {
+        init_propagator();
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let app = init_service(
+            App::new()
+                .wrap(middleware::from_fn(trace_response))
+                .wrap(TracingLogger::default())
+                .route("/", web::get().to(|| async { "ok" })),
+        )
+        .await;
+
+        let response = call_service(&app, TestRequest::get().uri("/").to_request()).await;
+
+        let header = response.headers().get(TRACERESPONSE_HEADER).unwrap();
+        assert_eq!(header.to_str().unwrap().len(), 55);
+    }
+}
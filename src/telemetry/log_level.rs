@@ -0,0 +1,160 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use crate::telemetry::error::TelemetryError;
+
+/// Reloadable filter layer installed by [`init_subscriber`](crate::telemetry::trace::init_subscriber)
+pub type ReloadableFilter = reload::Layer<EnvFilter, Registry>;
+
+/// Runtime handle to the subscriber's log filter.
+///
+/// Changes made with [`set`](Self::set) stick; changes made with
+/// [`set_for`](Self::set_for) revert to the standing directives after a TTL.
+#[derive(Clone)]
+pub struct LogLevelHandle {
+    handle: reload::Handle<EnvFilter, Registry>,
+    state: Arc<Mutex<State>>,
+}
+
+struct State {
+    /// Directives to revert to when a temporary change expires
+    standing: String,
+    /// Bumped on every change, so an expired TTL never undoes a newer change
+    generation: u64,
+}
+
+impl LogLevelHandle {
+    /// Wrap `filter` in a reloadable layer and return it with its handle
+    pub fn reloadable(filter: EnvFilter) -> (ReloadableFilter, Self) {
+        let standing = filter.to_string();
+        let (layer, handle) = reload::Layer::new(filter);
+        let state = State {
+            standing,
+            generation: 0,
+        };
+
+        (
+            layer,
+            Self {
+                handle,
+                state: Arc::new(Mutex::new(state)),
+            },
+        )
+    }
+
+    /// Directives currently in effect
+    pub fn current(&self) -> String {
+        self.handle
+            .with_current(|filter| filter.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Replace the directives until the next change
+    pub fn set(&self, directives: &str) -> Result<(), TelemetryError> {
+        let filter = parse(directives)?;
+        let mut state = self.state.lock().unwrap();
+        self.reload(filter)?;
+        state.standing = directives.to_string();
+        state.generation += 1;
+        Ok(())
+    }
+
+    /// Replace the directives for `ttl`, then revert to the standing ones.
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn set_for(&self, directives: &str, ttl: Duration) -> Result<(), TelemetryError> {
+        let filter = parse(directives)?;
+        let generation = {
+            let mut state = self.state.lock().unwrap();
+            self.reload(filter)?;
+            state.generation += 1;
+            state.generation
+        };
+
+        let handle = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(ttl).await;
+            handle.revert(generation);
+        });
+        Ok(())
+    }
+
+    /// Restore the standing directives if no change happened since `generation`
+    fn revert(&self, generation: u64) {
+        let state = self.state.lock().unwrap();
+        if state.generation != generation {
+            return;
+        }
+
+        match parse(&state.standing).and_then(|filter| self.reload(filter)) {
+            Ok(()) => tracing::info!(directives = %state.standing, "Log level reverted"),
+            Err(e) => tracing::error!(error = %e, "Failed to revert log level"),
+        }
+    }
+
+    fn reload(&self, filter: EnvFilter) -> Result<(), TelemetryError> {
+        self.handle
+            .reload(filter)
+            .map_err(|e| TelemetryError::Init(e.to_string()))
+    }
+}
+
+fn parse(directives: &str) -> Result<EnvFilter, TelemetryError> {
+    EnvFilter::try_new(directives)
+        .map_err(|e| TelemetryError::Config(format!("Invalid log directives: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// A handle whose layer is installed on a (non-global) registry
+    fn handle(directives: &str) -> (impl tracing::Subscriber, LogLevelHandle) {
+        let (layer, handle) = LogLevelHandle::reloadable(EnvFilter::new(directives));
+        (tracing_subscriber::registry().with(layer), handle)
+    }
+
+    #[test]
+    fn set_replaces_directives() {
+        let (_subscriber, handle) = handle("info");
+
+        handle.set("debug,h2=warn").unwrap();
+
+        assert_eq!(handle.current(), "h2=warn,debug");
+    }
+
+    #[test]
+    fn set_rejects_invalid_directives() {
+        let (_subscriber, handle) = handle("info");
+
+        let result = handle.set("foo=verbose");
+
+        assert!(matches!(result, Err(TelemetryError::Config(_))));
+        assert_eq!(handle.current(), "info");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn set_for_reverts_after_ttl() {
+        let (_subscriber, handle) = handle("info");
+
+        handle.set_for("trace", Duration::from_secs(60)).unwrap();
+        assert_eq!(handle.current(), "trace");
+
+        tokio::time::sleep(Duration::from_secs(61)).await;
+        assert_eq!(handle.current(), "info");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn newer_change_cancels_pending_revert() {
+        let (_subscriber, handle) = handle("info");
+
+        handle.set_for("trace", Duration::from_secs(60)).unwrap();
+        handle.set("warn").unwrap();
+
+        tokio::time::sleep(Duration::from_secs(61)).await;
+        assert_eq!(handle.current(), "warn");
+    }
+}
@@ -0,0 +1,89 @@
+use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
+use opentelemetry_sdk::logs::{LogExporter, SdkLogger, SdkLoggerProvider};
+use opentelemetry_sdk::Resource;
+use tracing::Subscriber;
+use tracing_subscriber::filter::{filter_fn, FilterFn};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Targets whose events must not be bridged: the exporters themselves log
+/// through them, and bridging would feed every export back into the pipeline.
+const EXPORTER_TARGETS: &[&str] = &["opentelemetry", "tonic", "h2", "hyper", "reqwest", "tower"];
+
+/// Build a logger provider that batches records to `exporter`
+pub fn build_batch_logger_provider<E>(exporter: E, resource: Resource) -> SdkLoggerProvider
+where
+    E: LogExporter + 'static,
+{
+    SdkLoggerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource)
+        .build()
+}
+
+/// Build the tracing → OpenTelemetry logs bridge layer.
+///
+/// Records carry the trace/span IDs of the active `tracing` span.
+pub fn build_log_bridge_layer<S>(provider: &SdkLoggerProvider) -> impl Layer<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    OpenTelemetryTracingBridge::<SdkLoggerProvider, SdkLogger>::new(provider)
+        .with_filter(exporter_target_filter())
+}
+
+fn exporter_target_filter() -> FilterFn<impl Fn(&tracing::Metadata<'_>) -> bool> {
+    filter_fn(|metadata| !is_exporter_target(metadata.target()))
+}
+
+fn is_exporter_target(target: &str) -> bool {
+    EXPORTER_TARGETS.iter().any(|prefix| {
+        target == *prefix
+            || target
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with("::") || rest.starts_with('_'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TracerProvider;
+    use opentelemetry_sdk::logs::InMemoryLogExporter;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn exporter_targets_are_excluded() {
+        assert!(is_exporter_target("hyper"));
+        assert!(is_exporter_target("hyper_util::client"));
+        assert!(is_exporter_target("opentelemetry_sdk"));
+        assert!(is_exporter_target("tonic::transport"));
+        assert!(!is_exporter_target("rust_cloud_run_service"));
+        assert!(!is_exporter_target("hyperloop"));
+    }
+
+    #[test]
+    fn bridge_exports_events_with_trace_context() {
+        let exporter = InMemoryLogExporter::default();
+        let logger_provider = SdkLoggerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let tracer_provider = SdkTracerProvider::builder().build();
+
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer("test")))
+            .with(build_log_bridge_layer(&logger_provider));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request");
+            let _entered = span.enter();
+            tracing::info!("handled");
+            tracing::info!(target: "hyper::proto", "internal");
+        });
+
+        let logs = exporter.get_emitted_logs().unwrap();
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].record.trace_context().is_some());
+    }
+}
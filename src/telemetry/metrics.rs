@@ -0,0 +1,52 @@
+use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+use opentelemetry_sdk::Resource;
+
+/// Build a meter provider that periodically pushes to `exporter`.
+///
+/// The export interval follows `OTEL_METRIC_EXPORT_INTERVAL` (60s by default).
+pub fn build_periodic_meter_provider<E>(exporter: E, resource: Resource) -> SdkMeterProvider
+where
+    E: opentelemetry_sdk::metrics::exporter::PushMetricExporter,
+{
+    let reader = PeriodicReader::builder(exporter).build();
+
+    SdkMeterProvider::builder()
+        .with_reader(reader)
+        .with_resource(resource)
+        .build()
+}
+
+/// Install the meter provider globally so `opentelemetry::global::meter` works
+pub fn init_meter_provider(provider: SdkMeterProvider) {
+    opentelemetry::global::set_meter_provider(provider);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::metrics::MeterProvider;
+    use opentelemetry_sdk::metrics::InMemoryMetricExporter;
+
+    #[test]
+    fn periodic_meter_provider_exports_on_flush() {
+        let exporter = InMemoryMetricExporter::default();
+        let provider = build_periodic_meter_provider(
+            exporter.clone(),
+            Resource::builder().with_service_name("test").build(),
+        );
+
+        let counter = provider.meter("test").u64_counter("requests").build();
+        counter.add(3, &[]);
+        provider.force_flush().unwrap();
+
+        let metrics = exporter.get_finished_metrics().unwrap();
+        let names: Vec<_> = metrics
+            .iter()
+            .flat_map(|rm| rm.scope_metrics())
+            .flat_map(|sm| sm.metrics())
+            .map(|m| m.name().to_string())
+            .collect();
+
+        assert_eq!(names, vec!["requests".to_string()]);
+    }
+}
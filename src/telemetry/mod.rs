@@ -1,17 +1,27 @@
 //! Modular telemetry with pluggable providers.
 //!
 //! This module provides a flexible telemetry system built on OpenTelemetry
-//! with support for multiple backends via feature flags.
+//! with support for multiple backends via feature flags. Each backend exports
+//! both traces and metrics; the meter provider is installed globally, so
+//! instruments come from `opentelemetry::global::meter("...")`.
 //!
 //! # Features
 //!
 //! - `telemetry-gcp`: Enable GCP Cloud Trace support
+//! - `telemetry-aws`: Enable AWS X-Ray support (via an ADOT collector)
+//! - `telemetry-zipkin`: Enable Zipkin export and B3 propagation
+//! - `telemetry-yaml`: Accept YAML config files
 //!
 //! # Quick Start
 //!
 //! ```rust,ignore
 //! // Initialize from environment (uses Local backend by default)
-//! telemetry::init().await?;
+//! let guard = telemetry::init().await?;
+//!
+//! // ... run the service ...
+//!
+//! // Flush buffered spans before exiting
+//! guard.shutdown().await?;
 //! ```
 //!
 //! # Configuration
@@ -29,36 +39,104 @@
 //!     .json()  // JSON logs for cloud
 //!     .build();
 //!
-//! telemetry::init_with_config(&config).await?;
+//! let guard = telemetry::init_with_config(&config).await?;
 //! ```
 //!
+//! ## Using a Config File
+//!
+//! [`init`] reads the TOML file (or YAML with `telemetry-yaml`) named by
+//! `TELEMETRY_CONFIG_FILE` (see [`TelemetryConfig::load`]). Every key is optional:
+//!
+//! ```toml
+//! service_name = "my-service"
+//! otlp_endpoint = "http://collector:4317"
+//! log_format = "json"
+//! sampler = { name = "parentbased_traceidratio", ratio = 0.1 }
+//!
+//! [otlp_export]
+//! timeout_ms = 5000
+//! headers = { authorization = "Bearer token" }
+//!
+//! [resource_attributes]
+//! "deployment.environment.name" = "prod"
+//!
+//! [backend.gcp]
+//! project_id = "my-project"
+//! ```
+//!
+//! Precedence, lowest first: built-in defaults, the config file, environment
+//! variables (only those that are set), then `with_*` calls in code.
+//!
 //! ## Log Formats
 //!
 //! - [`LogFormat::Pretty`]: Human-readable with colors (default for local dev)
 //! - [`LogFormat::Json`]: Structured JSON (for cloud environments)
+//! - `LogFormat::GcpJson`: Cloud Logging structured JSON with `severity` and
+//!   trace correlation fields (requires `telemetry-gcp`)
+//!
+//! Stdout output is always on. OTLP log export is toggled separately with
+//! [`TelemetryConfigBuilder::export_logs`] or `OTEL_LOGS_EXPORTER=otlp`.
 //!
 //! ## Backends
 //!
 //! - [`TelemetryBackend::Local`]: Local development with optional OTLP export
 //! - [`TelemetryBackend::Gcp`]: GCP Cloud Trace (requires `telemetry-gcp` feature)
+//! - `TelemetryBackend::Aws`: AWS X-Ray through an ADOT collector (requires `telemetry-aws` feature)
+//! - `TelemetryBackend::Zipkin`: Traces to a Zipkin server (requires `telemetry-zipkin` feature)
+//! - [`TelemetryBackend::Custom`]: Provider from another crate (see [`registry`])
 //!
 //! # Environment Variables
 //!
 //! | Variable | Description | Default |
 //! |----------|-------------|---------|
+//! | `TELEMETRY_CONFIG_FILE` | TOML or YAML (`telemetry-yaml`) config file, overridden by the variables below | - |
 //! | `OTEL_SERVICE_NAME` | Service name | `CARGO_PKG_NAME` |
 //! | `OTEL_SERVICE_VERSION` | Service version | `CARGO_PKG_VERSION` |
+//! | `OTEL_RESOURCE_ATTRIBUTES` | Extra resource attributes, `key=value,...` (percent-encoded) | - |
 //! | `OTEL_EXPORTER_OTLP_ENDPOINT` | OTLP endpoint | - |
+//! | `OTEL_EXPORTER_OTLP_PROTOCOL` | `grpc`, `http/protobuf` or `http/json` | `grpc` |
+//! | `OTEL_EXPORTER_OTLP_HEADERS` | Exporter headers, `key=value,...` (percent-encoded) | - |
+//! | `OTEL_EXPORTER_OTLP_TIMEOUT` | Export timeout in milliseconds | `10000` |
+//! | `OTEL_EXPORTER_OTLP_COMPRESSION` | `gzip` or `none` | `none` |
+//! | `OTEL_EXPORTER_OTLP_TRACES_{HEADERS,TIMEOUT,COMPRESSION}` | Trace-only overrides of the above | - |
+//! | `OTEL_EXPORTER_OTLP_CERTIFICATE` | PEM file of extra trusted CAs (also for Cloud Trace) | - |
+//! | `OTEL_EXPORTER_OTLP_CLIENT_CERTIFICATE` | PEM client certificate for mTLS (not applied to Cloud Trace) | - |
+//! | `OTEL_EXPORTER_OTLP_CLIENT_KEY` | PEM client key for mTLS | - |
+//! | `OTEL_EXPORTER_OTLP_INSECURE` | `true` for plaintext gRPC / unverified HTTPS (not applied to Cloud Trace) | `false` |
 //! | `RUST_LOG` | Log level filter | `info` |
-//! | `LOG_FORMAT` | `pretty` or `json` | `pretty` |
+//! | `LOG_FORMAT` | `pretty`, `json` or `gcp_json` | `pretty` |
+//! | `OTEL_LOGS_EXPORTER` | `otlp` to also export logs over OTLP | `none` |
+//! | `OTEL_TRACES_EXPORTER` | `otlp`, `console` (print spans to stdout) or `none` | `otlp` |
+//! | `OTEL_EXPORTER_ZIPKIN_ENDPOINT` | Zipkin span collection URL; selects the Zipkin backend | - |
+//! | `OTEL_PROPAGATORS` | `b3` and/or `b3multi` to pick the B3 headers written (`telemetry-zipkin`) | `b3multi` |
+//! | `TELEMETRY_BACKEND` | Name of a backend registered with [`register_backend`] | - |
+//! | `TELEMETRY_FAILURE_POLICY` | `fail_fast`, `fallback_to_local` or `retry_in_background` when the backend can't start | `fail_fast` (the server binary: `fallback_to_local`) |
+//! | `OTEL_TRACES_SAMPLER` | `always_on`, `always_off`, `traceidratio` or `parentbased_*` | `parentbased_always_on` |
+//! | `OTEL_TRACES_SAMPLER_ARG` | Ratio for the `*traceidratio` samplers | `1.0` |
 //!
 //! # Module Structure
 //!
 //! - [`api`]: Core trait and initialization functions
 //! - [`config`]: Configuration types
 //! - [`error`]: Error types
+//! - [`fallback`]: Local telemetry and background retry when the backend can't start
+//! - [`fanout`]: Secondary backends that receive spans alongside the main one
+//! - [`guard`]: Shutdown guard that flushes providers
+//! - [`http`]: actix-web `traceresponse` middleware
+//! - [`log_level`]: Runtime-adjustable log filter
+//! - [`logs`]: Logger provider and tracing → OpenTelemetry logs bridge
+//! - [`metrics`]: Meter provider helpers
+//! - [`propagation`]: Context propagators (W3C trace context and baggage,
+//!   `X-Cloud-Trace-Context`)
+//! - [`otlp`]: OTLP exporter headers, timeout, compression and TLS
+//! - [`registry`]: Custom backends from other crates, selectable by name
+//! - [`resource`]: Resource attributes and detectors (host, process, OS, container)
+//! - [`sampling`]: Trace sampler configuration
+//! - `testing`: In-memory provider and span assertions for tests (test builds only)
 //! - [`default`]: Local/default provider
 //! - [`gcp`]: GCP Cloud Trace provider (feature-gated)
+//! - `aws`: AWS X-Ray provider (feature-gated)
+//! - `zipkin`: Zipkin provider and B3 propagator (feature-gated)
 
 #![allow(dead_code, unused_imports)] // Public API - not all items used internally
 
@@ -66,18 +144,54 @@ pub mod api;
 pub mod config;
 pub mod default;
 pub mod error;
+pub mod fallback;
+pub mod fanout;
+pub mod guard;
+pub mod http;
+pub mod log_level;
+pub mod logs;
+pub mod metrics;
+pub mod otlp;
+pub mod propagation;
+pub mod registry;
 pub mod resource;
+pub mod sampling;
 pub mod trace;
 
+#[cfg(test)]
+pub mod testing;
+
 #[cfg(feature = "telemetry-gcp")]
 pub mod gcp;
+
+#[cfg(feature = "telemetry-aws")]
+pub mod aws;
+
+#[cfg(feature = "telemetry-zipkin")]
+pub mod zipkin;
+
 #[cfg(feature = "telemetry-gcp")]
 pub use gcp::{GcpConfig, GcpPlatform};
+#[cfg(feature = "telemetry-aws")]
+pub use aws::{AwsConfig, AwsPlatform};
+#[cfg(feature = "telemetry-zipkin")]
+pub use zipkin::ZipkinConfig;
 
 // Re-exports
-pub use api::{init, init_with_config, init_with_provider, TelemetryProvider};
-pub use config::{LogFormat, TelemetryBackend, TelemetryConfig, TelemetryConfigBuilder};
-pub use error::TelemetryError;
+pub use api::{init, init_with_config, init_with_detection, init_with_provider, TelemetryProvider};
+pub use config::{
+    FailurePolicy, LogFormat, OtlpProtocol, TelemetryBackend, TelemetryConfig,
+    TelemetryConfigBuilder, TracesExporter,
+};
+pub use error::{ConfigProblem, TelemetryError};
+pub use fanout::SecondaryBackend;
+pub use guard::TelemetryGuard;
+pub use http::trace_response;
+pub use log_level::LogLevelHandle;
+pub use otlp::{OtlpCompression, OtlpExportConfig, OtlpTlsConfig};
+pub use registry::{register_backend, CustomBackend, DynTelemetryProvider};
+pub use resource::ResourceDetector;
+pub use sampling::SamplerConfig;
 
 
 
@@ -0,0 +1,535 @@
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use opentelemetry_otlp::{Compression, WithExportConfig, WithHttpConfig, WithTonicConfig};
+use percent_encoding::percent_decode_str;
+use serde::{Deserialize, Deserializer};
+use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
+use tonic::transport::{Certificate, ClientTlsConfig, Identity};
+
+use crate::telemetry::error::TelemetryError;
+
+/// Exporter timeout used when none is configured (the OTLP spec default)
+const DEFAULT_EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// PEM-encoded certificate chain and private key
+type PemIdentity = (Vec<u8>, Vec<u8>);
+
+/// Exporter payload compression (values of `OTEL_EXPORTER_OTLP_COMPRESSION`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OtlpCompression {
+    Gzip,
+    /// Explicitly uncompressed, e.g. to override a general `gzip` for one signal
+    None,
+}
+
+impl OtlpCompression {
+    fn to_otlp(self) -> Option<Compression> {
+        match self {
+            Self::Gzip => Some(Compression::Gzip),
+            Self::None => None,
+        }
+    }
+}
+
+/// Headers, timeout and compression applied to OTLP exporters.
+///
+/// Every field is optional: unset fields fall back to the all-signals
+/// settings (see [`OtlpExportConfig::or`]) and then to the exporter defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OtlpExportConfig {
+    /// A `name = "value"` table in config files
+    #[serde(deserialize_with = "deserialize_headers")]
+    pub headers: Option<Vec<(String, String)>>,
+    /// `timeout_ms` in config files
+    #[serde(rename = "timeout_ms", deserialize_with = "deserialize_millis")]
+    pub timeout: Option<Duration>,
+    pub compression: Option<OtlpCompression>,
+}
+
+impl OtlpExportConfig {
+    /// Read the all-signals `OTEL_EXPORTER_OTLP_{HEADERS,TIMEOUT,COMPRESSION}`
+    pub fn from_env() -> Self {
+        Self::from_env_vars(
+            "OTEL_EXPORTER_OTLP_HEADERS",
+            "OTEL_EXPORTER_OTLP_TIMEOUT",
+            "OTEL_EXPORTER_OTLP_COMPRESSION",
+        )
+    }
+
+    /// Read the trace-only `OTEL_EXPORTER_OTLP_TRACES_{HEADERS,TIMEOUT,COMPRESSION}`
+    pub fn traces_from_env() -> Self {
+        Self::from_env_vars(
+            "OTEL_EXPORTER_OTLP_TRACES_HEADERS",
+            "OTEL_EXPORTER_OTLP_TRACES_TIMEOUT",
+            "OTEL_EXPORTER_OTLP_TRACES_COMPRESSION",
+        )
+    }
+
+    fn from_env_vars(headers_var: &str, timeout_var: &str, compression_var: &str) -> Self {
+        Self {
+            headers: env::var(headers_var)
+                .ok()
+                .map(|value| parse_headers(&value)),
+            timeout: env::var(timeout_var)
+                .ok()
+                .and_then(|value| value.trim().parse().ok())
+                .map(Duration::from_millis),
+            compression: env::var(compression_var)
+                .ok()
+                .and_then(|value| parse_compression(&value)),
+        }
+    }
+
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers
+            .get_or_insert_with(Vec::new)
+            .push((key.into(), value.into()));
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_compression(mut self, compression: OtlpCompression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Fill unset fields from `fallback` (signal-specific settings win, as in the spec)
+    pub fn or(&self, fallback: &Self) -> Self {
+        Self {
+            headers: self.headers.clone().or_else(|| fallback.headers.clone()),
+            timeout: self.timeout.or(fallback.timeout),
+            compression: self.compression.or(fallback.compression),
+        }
+    }
+
+    /// Apply the settings to a gRPC exporter builder
+    pub(crate) fn apply_tonic<B>(&self, mut builder: B) -> B
+    where
+        B: WithExportConfig + WithTonicConfig,
+    {
+        if let Some(headers) = &self.headers {
+            builder = builder.with_metadata(metadata(headers));
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.with_timeout(timeout);
+        }
+        if let Some(compression) = self.compression.and_then(OtlpCompression::to_otlp) {
+            builder = builder.with_compression(compression);
+        }
+        builder
+    }
+
+    /// Apply the settings to an HTTP exporter builder
+    pub(crate) fn apply_http<B>(&self, mut builder: B) -> B
+    where
+        B: WithExportConfig + WithHttpConfig,
+    {
+        if let Some(headers) = &self.headers {
+            builder = builder.with_headers(headers.iter().cloned().collect::<HashMap<_, _>>());
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.with_timeout(timeout);
+        }
+        if let Some(compression) = self.compression.and_then(OtlpCompression::to_otlp) {
+            builder = builder.with_compression(compression);
+        }
+        builder
+    }
+}
+
+/// TLS settings for OTLP exporters.
+///
+/// Server certificates are checked against the platform roots plus
+/// `ca_certificate`; `client_certificate` and `client_key` enable mutual TLS.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OtlpTlsConfig {
+    /// PEM bundle of extra trusted CAs
+    pub ca_certificate: Option<PathBuf>,
+    /// PEM client certificate chain (mTLS)
+    pub client_certificate: Option<PathBuf>,
+    /// PEM private key of the client certificate (mTLS)
+    pub client_key: Option<PathBuf>,
+    /// Name to verify the server certificate against, instead of the endpoint host
+    pub domain_name: Option<String>,
+    /// Send in plaintext (gRPC) or skip certificate checks (HTTP)
+    pub insecure: bool,
+}
+
+impl OtlpTlsConfig {
+    /// Read `OTEL_EXPORTER_OTLP_{CERTIFICATE,CLIENT_CERTIFICATE,CLIENT_KEY,INSECURE}`
+    pub fn from_env() -> Self {
+        Self::default().with_env_overrides()
+    }
+
+    /// Replace each setting whose variable is set, keeping the others
+    pub fn with_env_overrides(mut self) -> Self {
+        if let Some(path) = env::var_os("OTEL_EXPORTER_OTLP_CERTIFICATE") {
+            self.ca_certificate = Some(path.into());
+        }
+        if let Some(path) = env::var_os("OTEL_EXPORTER_OTLP_CLIENT_CERTIFICATE") {
+            self.client_certificate = Some(path.into());
+        }
+        if let Some(path) = env::var_os("OTEL_EXPORTER_OTLP_CLIENT_KEY") {
+            self.client_key = Some(path.into());
+        }
+        if let Ok(value) = env::var("OTEL_EXPORTER_OTLP_INSECURE") {
+            self.insecure = value.trim().eq_ignore_ascii_case("true");
+        }
+        self
+    }
+
+    pub fn with_ca_certificate(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca_certificate = Some(path.into());
+        self
+    }
+
+    /// Present a client certificate (mutual TLS)
+    pub fn with_client_identity(
+        mut self,
+        certificate: impl Into<PathBuf>,
+        key: impl Into<PathBuf>,
+    ) -> Self {
+        self.client_certificate = Some(certificate.into());
+        self.client_key = Some(key.into());
+        self
+    }
+
+    pub fn with_domain_name(mut self, domain_name: impl Into<String>) -> Self {
+        self.domain_name = Some(domain_name.into());
+        self
+    }
+
+    pub fn with_insecure(mut self, insecure: bool) -> Self {
+        self.insecure = insecure;
+        self
+    }
+
+    /// Whether any setting differs from the defaults
+    pub fn is_configured(&self) -> bool {
+        self != &Self::default()
+    }
+
+    /// Build the gRPC TLS config, or `None` for a plaintext channel
+    pub(crate) fn tonic_config(&self) -> Result<Option<ClientTlsConfig>, TelemetryError> {
+        if self.insecure {
+            return Ok(None);
+        }
+
+        let mut tls = self.trusted_roots()?;
+        if let Some((certificate, key)) = self.client_identity()? {
+            tls = tls.identity(Identity::from_pem(certificate, key));
+        }
+        if let Some(domain_name) = &self.domain_name {
+            tls = tls.domain_name(domain_name);
+        }
+
+        Ok(Some(tls))
+    }
+
+    /// gRPC TLS config trusting the platform roots plus `ca_certificate`
+    ///
+    /// Used for Google endpoints, where the extra CA lets a TLS-intercepting
+    /// egress proxy through but the server is still verified as its own host.
+    pub(crate) fn trusted_roots(&self) -> Result<ClientTlsConfig, TelemetryError> {
+        let mut tls = ClientTlsConfig::new().with_native_roots();
+        if let Some(path) = &self.ca_certificate {
+            tls = tls.ca_certificate(Certificate::from_pem(read_pem(path)?));
+        }
+        Ok(tls)
+    }
+
+    /// Build a blocking HTTP client honouring these settings
+    pub(crate) fn http_client(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<reqwest::blocking::Client, TelemetryError> {
+        if self.domain_name.is_some() {
+            return Err(TelemetryError::Config(
+                "A TLS domain override is only supported with the gRPC protocol".to_string(),
+            ));
+        }
+
+        let mut builder = reqwest::blocking::Client::builder()
+            .timeout(timeout.unwrap_or(DEFAULT_EXPORT_TIMEOUT))
+            .danger_accept_invalid_certs(self.insecure);
+
+        if let Some(path) = &self.ca_certificate {
+            for certificate in reqwest::Certificate::from_pem_bundle(&read_pem(path)?)
+                .map_err(|e| tls_error(path, e))?
+            {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        if let Some((certificate, key)) = self.client_identity()? {
+            let identity = reqwest::Identity::from_pem(&[certificate, key].concat())
+                .map_err(|e| TelemetryError::Config(format!("Invalid client identity: {}", e)))?;
+            builder = builder.identity(identity);
+        }
+
+        // The blocking client owns a runtime, which can't be created from async code
+        std::thread::spawn(move || builder.build())
+            .join()
+            .map_err(|_| TelemetryError::Exporter("HTTP client builder panicked".to_string()))?
+            .map_err(|e| TelemetryError::Exporter(e.to_string()))
+    }
+
+    /// Read the client certificate and key, which must be set together
+    fn client_identity(&self) -> Result<Option<PemIdentity>, TelemetryError> {
+        match (&self.client_certificate, &self.client_key) {
+            (Some(certificate), Some(key)) => Ok(Some((read_pem(certificate)?, read_pem(key)?))),
+            (None, None) => Ok(None),
+            _ => Err(TelemetryError::Config(
+                "Client certificate and client key must be set together".to_string(),
+            )),
+        }
+    }
+}
+
+fn read_pem(path: &Path) -> Result<Vec<u8>, TelemetryError> {
+    fs::read(path).map_err(|e| tls_error(path, e))
+}
+
+fn tls_error(path: &Path, error: impl std::fmt::Display) -> TelemetryError {
+    TelemetryError::Config(format!("Failed to load {}: {}", path.display(), error))
+}
+
+/// Parse `key1=value1,key2=value2`, percent-decoding keys and values.
+///
+/// Malformed entries are skipped rather than failing the whole list.
+pub fn parse_headers(value: &str) -> Vec<(String, String)> {
+    value
+        .split(',')
+        .filter_map(|entry| {
+            let (key, value) = entry.split_once('=')?;
+            let key = percent_decode_str(key.trim()).decode_utf8().ok()?;
+            let value = percent_decode_str(value.trim()).decode_utf8().ok()?;
+            (!key.is_empty()).then(|| (key.into_owned(), value.into_owned()))
+        })
+        .collect()
+}
+
+/// Parse a compression name; unknown values are treated as unset
+pub fn parse_compression(value: &str) -> Option<OtlpCompression> {
+    match value.trim() {
+        "gzip" => Some(OtlpCompression::Gzip),
+        "none" => Some(OtlpCompression::None),
+        _ => None,
+    }
+}
+
+fn deserialize_headers<'de, D>(deserializer: D) -> Result<Option<Vec<(String, String)>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let headers = Option::<BTreeMap<String, String>>::deserialize(deserializer)?;
+    Ok(headers.map(|headers| headers.into_iter().collect()))
+}
+
+fn deserialize_millis<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_millis))
+}
+
+/// Convert headers to gRPC metadata, dropping names or values gRPC rejects
+fn metadata(headers: &[(String, String)]) -> MetadataMap {
+    let mut metadata = MetadataMap::new();
+    for (key, value) in headers {
+        let key = MetadataKey::from_bytes(key.to_ascii_lowercase().as_bytes());
+        let value = MetadataValue::try_from(value.as_str());
+        if let (Ok(key), Ok(value)) = (key, value) {
+            metadata.insert(key, value);
+        }
+    }
+    metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::testing::EnvGuard;
+
+    #[test]
+    fn parses_header_list() {
+        assert_eq!(
+            parse_headers("api-key=secret, x-tenant = acme"),
+            vec![
+                ("api-key".to_string(), "secret".to_string()),
+                ("x-tenant".to_string(), "acme".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn percent_decodes_headers() {
+        assert_eq!(
+            parse_headers("authorization=Basic%20dXNlcjpwYXNz"),
+            vec![(
+                "authorization".to_string(),
+                "Basic dXNlcjpwYXNz".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn skips_malformed_headers() {
+        assert_eq!(
+            parse_headers("no-equals,=empty-key,ok=1"),
+            vec![("ok".to_string(), "1".to_string())]
+        );
+    }
+
+    #[test]
+    fn parses_compression() {
+        assert_eq!(parse_compression("gzip"), Some(OtlpCompression::Gzip));
+        assert_eq!(parse_compression("none"), Some(OtlpCompression::None));
+        assert_eq!(parse_compression("brotli"), None);
+    }
+
+    #[test]
+    fn signal_compression_none_overrides_general_gzip() {
+        let general = OtlpExportConfig::default().with_compression(OtlpCompression::Gzip);
+        let traces = OtlpExportConfig::default().with_compression(OtlpCompression::None);
+
+        let merged = traces.or(&general);
+
+        assert_eq!(merged.compression, Some(OtlpCompression::None));
+        assert_eq!(
+            merged.compression.and_then(OtlpCompression::to_otlp),
+            None
+        );
+    }
+
+    #[test]
+    fn deserializes_compression_none() {
+        let export: OtlpExportConfig = toml::from_str("compression = \"none\"").unwrap();
+
+        assert_eq!(export.compression, Some(OtlpCompression::None));
+    }
+
+    #[test]
+    fn signal_settings_override_general_ones() {
+        let general = OtlpExportConfig::default()
+            .with_header("api-key", "general")
+            .with_timeout(Duration::from_secs(10))
+            .with_compression(OtlpCompression::Gzip);
+        let traces = OtlpExportConfig::default().with_header("api-key", "traces");
+
+        let merged = traces.or(&general);
+
+        assert_eq!(
+            merged.headers,
+            Some(vec![("api-key".to_string(), "traces".to_string())])
+        );
+        assert_eq!(merged.timeout, Some(Duration::from_secs(10)));
+        assert_eq!(merged.compression, Some(OtlpCompression::Gzip));
+    }
+
+    #[test]
+    fn from_env_reads_traces_variants() {
+        let _guard = EnvGuard::new(&[
+            "OTEL_EXPORTER_OTLP_TRACES_HEADERS",
+            "OTEL_EXPORTER_OTLP_TRACES_TIMEOUT",
+            "OTEL_EXPORTER_OTLP_TRACES_COMPRESSION",
+        ]);
+        env::set_var("OTEL_EXPORTER_OTLP_TRACES_HEADERS", "x-env-key=abc");
+        env::set_var("OTEL_EXPORTER_OTLP_TRACES_TIMEOUT", "2500");
+        env::set_var("OTEL_EXPORTER_OTLP_TRACES_COMPRESSION", "gzip");
+
+        let traces = OtlpExportConfig::traces_from_env();
+
+        assert_eq!(
+            traces.headers,
+            Some(vec![("x-env-key".to_string(), "abc".to_string())])
+        );
+        assert_eq!(traces.timeout, Some(Duration::from_millis(2500)));
+        assert_eq!(traces.compression, Some(OtlpCompression::Gzip));
+    }
+
+    #[test]
+    fn tls_from_env_reads_paths_and_flags() {
+        let _guard = EnvGuard::new(&[
+            "OTEL_EXPORTER_OTLP_CERTIFICATE",
+            "OTEL_EXPORTER_OTLP_CLIENT_CERTIFICATE",
+            "OTEL_EXPORTER_OTLP_CLIENT_KEY",
+            "OTEL_EXPORTER_OTLP_INSECURE",
+        ]);
+        env::set_var("OTEL_EXPORTER_OTLP_CERTIFICATE", "/etc/otel/ca.pem");
+        env::set_var(
+            "OTEL_EXPORTER_OTLP_CLIENT_CERTIFICATE",
+            "/etc/otel/client.pem",
+        );
+        env::set_var("OTEL_EXPORTER_OTLP_CLIENT_KEY", "/etc/otel/client.key");
+        env::set_var("OTEL_EXPORTER_OTLP_INSECURE", "TRUE");
+
+        let tls = OtlpTlsConfig::from_env();
+
+        assert_eq!(
+            tls,
+            OtlpTlsConfig::default()
+                .with_ca_certificate("/etc/otel/ca.pem")
+                .with_client_identity("/etc/otel/client.pem", "/etc/otel/client.key")
+                .with_insecure(true)
+        );
+    }
+
+    #[test]
+    fn insecure_tls_is_plaintext_grpc() {
+        let tls = OtlpTlsConfig::default().with_insecure(true);
+
+        assert!(tls.is_configured());
+        assert!(tls.tonic_config().unwrap().is_none());
+    }
+
+    #[test]
+    fn trusted_roots_reads_ca_even_when_insecure() {
+        let tls = OtlpTlsConfig::default()
+            .with_ca_certificate("/nonexistent/ca.pem")
+            .with_insecure(true);
+
+        assert!(matches!(tls.trusted_roots(), Err(TelemetryError::Config(_))));
+    }
+
+    #[test]
+    fn client_certificate_requires_key() {
+        let tls = OtlpTlsConfig {
+            client_certificate: Some(PathBuf::from("/etc/otel/client.pem")),
+            ..Default::default()
+        };
+
+        assert!(matches!(tls.tonic_config(), Err(TelemetryError::Config(_))));
+    }
+
+    #[test]
+    fn http_client_rejects_domain_override() {
+        let tls = OtlpTlsConfig::default().with_domain_name("collector.internal");
+
+        assert!(matches!(
+            tls.http_client(None),
+            Err(TelemetryError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn metadata_drops_invalid_entries() {
+        let metadata = metadata(&[
+            ("API-Key".to_string(), "secret".to_string()),
+            ("bad header".to_string(), "x".to_string()),
+        ]);
+
+        assert_eq!(metadata.get("api-key").unwrap(), "secret");
+        assert_eq!(metadata.len(), 1);
+    }
+}
@@ -0,0 +1,211 @@
+use opentelemetry::propagation::{TextMapCompositePropagator, TextMapPropagator};
+use opentelemetry_sdk::propagation::{BaggagePropagator, TraceContextPropagator};
+
+/// Build the composite propagator installed during initialization.
+///
+/// W3C `traceparent`/`tracestate` and `baggage` are always handled. With
+/// `telemetry-gcp`, the `X-Cloud-Trace-Context` header sent by GCP front ends
+/// is handled too, with `telemetry-aws` the `X-Amzn-Trace-Id` header, and
+/// with `telemetry-zipkin` the B3 headers; `traceparent` is registered after
+/// them so it wins when both are present.
+pub fn build_propagator() -> TextMapCompositePropagator {
+    let propagators: Vec<Box<dyn TextMapPropagator + Send + Sync>> = vec![
+        #[cfg(feature = "telemetry-aws")]
+        Box::new(crate::telemetry::aws::propagator::XrayPropagator::new()),
+        #[cfg(feature = "telemetry-gcp")]
+        Box::new(crate::telemetry::gcp::propagator::CloudTraceContextPropagator::new()),
+        #[cfg(feature = "telemetry-zipkin")]
+        Box::new(crate::telemetry::zipkin::B3Propagator::with_encoding(
+            crate::telemetry::zipkin::B3Encoding::from_env(),
+        )),
+        Box::new(TraceContextPropagator::new()),
+        Box::new(BaggagePropagator::new()),
+    ];
+
+    TextMapCompositePropagator::new(propagators)
+}
+
+/// Install the propagator globally (used by `tracing-actix-web` root spans)
+pub fn init_propagator() {
+    opentelemetry::global::set_text_map_propagator(build_propagator());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TraceContextExt;
+    use std::collections::HashMap;
+
+    const TRACEPARENT_TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+    fn carrier(headers: &[(&str, &str)]) -> HashMap<String, String> {
+        headers
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn extracted_trace_id(headers: &[(&str, &str)]) -> String {
+        let cx = build_propagator().extract(&carrier(headers));
+        cx.span().span_context().trace_id().to_string()
+    }
+
+    #[test]
+    fn extracts_traceparent() {
+        let trace_id = extracted_trace_id(&[(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        )]);
+
+        assert_eq!(trace_id, TRACEPARENT_TRACE_ID);
+    }
+
+    #[test]
+    fn extracts_baggage() {
+        use opentelemetry::baggage::BaggageExt;
+
+        let cx = build_propagator().extract(&carrier(&[("baggage", "tenant=acme,region=eu")]));
+
+        assert_eq!(cx.baggage().get("tenant").unwrap().as_str(), "acme");
+        assert_eq!(cx.baggage().len(), 2);
+    }
+
+    #[cfg(feature = "telemetry-gcp")]
+    #[test]
+    fn extracts_cloud_trace_context() {
+        let trace_id = extracted_trace_id(&[(
+            "x-cloud-trace-context",
+            "105445aa7843bc8bf206b12000100000/1;o=1",
+        )]);
+
+        assert_eq!(trace_id, "105445aa7843bc8bf206b12000100000");
+    }
+
+    #[cfg(feature = "telemetry-gcp")]
+    #[test]
+    fn traceparent_wins_over_cloud_trace_context() {
+        let trace_id = extracted_trace_id(&[
+            (
+                "x-cloud-trace-context",
+                "105445aa7843bc8bf206b12000100000/1;o=1",
+            ),
+            (
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            ),
+        ]);
+
+        assert_eq!(trace_id, TRACEPARENT_TRACE_ID);
+    }
+
+    #[cfg(feature = "telemetry-aws")]
+    #[test]
+    fn extracts_xray_header() {
+        let trace_id = extracted_trace_id(&[(
+            "x-amzn-trace-id",
+            "Root=1-5759e988-bd862e3fe1be46a994272793;Parent=53995c3f42cd8ad8;Sampled=1",
+        )]);
+
+        assert_eq!(trace_id, "5759e988bd862e3fe1be46a994272793");
+    }
+
+    #[cfg(feature = "telemetry-aws")]
+    #[test]
+    fn traceparent_wins_over_xray_header() {
+        let trace_id = extracted_trace_id(&[
+            (
+                "x-amzn-trace-id",
+                "Root=1-5759e988-bd862e3fe1be46a994272793;Parent=53995c3f42cd8ad8;Sampled=1",
+            ),
+            (
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            ),
+        ]);
+
+        assert_eq!(trace_id, TRACEPARENT_TRACE_ID);
+    }
+
+    #[cfg(feature = "telemetry-zipkin")]
+    #[test]
+    fn extracts_b3_headers() {
+        let single = extracted_trace_id(&[(
+            "b3",
+            "80f198ee56343ba864fe8b2a57d3eff7-e457b5a2e4d86bd1-1",
+        )]);
+        let multi = extracted_trace_id(&[
+            ("x-b3-traceid", "80f198ee56343ba864fe8b2a57d3eff7"),
+            ("x-b3-spanid", "e457b5a2e4d86bd1"),
+            ("x-b3-sampled", "1"),
+        ]);
+
+        assert_eq!(single, "80f198ee56343ba864fe8b2a57d3eff7");
+        assert_eq!(multi, "80f198ee56343ba864fe8b2a57d3eff7");
+    }
+
+    #[cfg(feature = "telemetry-zipkin")]
+    #[test]
+    fn traceparent_wins_over_b3_header() {
+        let trace_id = extracted_trace_id(&[
+            ("b3", "80f198ee56343ba864fe8b2a57d3eff7-e457b5a2e4d86bd1-1"),
+            (
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            ),
+        ]);
+
+        assert_eq!(trace_id, TRACEPARENT_TRACE_ID);
+    }
+
+    #[cfg(feature = "telemetry-gcp")]
+    #[test]
+    fn injects_both_headers() {
+        let cx = build_propagator().extract(&carrier(&[(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        )]));
+        let mut injected = HashMap::new();
+
+        build_propagator().inject_context(&cx, &mut injected);
+
+        assert!(injected.contains_key("traceparent"));
+        assert!(injected["x-cloud-trace-context"].starts_with(TRACEPARENT_TRACE_ID));
+    }
+
+    #[cfg(feature = "telemetry-gcp")]
+    #[actix_web::test]
+    async fn actix_root_span_joins_cloud_trace_context() {
+        use actix_web::{test, web, App};
+        use opentelemetry::trace::TracerProvider;
+        use opentelemetry_sdk::trace::SdkTracerProvider;
+        use tracing_actix_web::TracingLogger;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        init_propagator();
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let app = test::init_service(App::new().wrap(TracingLogger::default()).route(
+            "/",
+            web::get().to(|| async {
+                let cx = tracing::Span::current().context();
+                cx.span().span_context().trace_id().to_string()
+            }),
+        ))
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/")
+            .insert_header((
+                "X-Cloud-Trace-Context",
+                "105445aa7843bc8bf206b12000100000/1;o=1",
+            ))
+            .to_request();
+        let body = test::call_and_read_body(&app, req).await;
+
+        assert_eq!(body, "105445aa7843bc8bf206b12000100000");
+    }
+}
@@ -0,0 +1,246 @@
+//! Backends supplied by other crates.
+//!
+//! Implement [`TelemetryProvider`] for an in-house exporter, then either pass
+//! it in code as [`TelemetryBackend::Custom`](crate::telemetry::TelemetryBackend::Custom):
+//!
+//! ```ignore
+//! let config = TelemetryConfig::new("my-service", "1.0")
+//!     .with_backend(TelemetryBackend::Custom(CustomBackend::new("acme", AcmeProvider)));
+//! ```
+//!
+//! or register it under a name before calling [`init`](crate::telemetry::init),
+//! so configuration can select it:
+//!
+//! ```ignore
+//! register_backend("acme", AcmeProvider);
+//! let _guard = telemetry::init().await?; // with TELEMETRY_BACKEND=acme
+//! ```
+//!
+//! A registered name is selected with `TELEMETRY_BACKEND=<name>`, or with
+//! `backend.custom = "<name>"` in a config file; an unregistered name fails
+//! validation either way. Custom backends get the same
+//! [`FailurePolicy`](crate::telemetry::FailurePolicy) handling as GCP, minus
+//! the background retry, and can't be used as secondary backends.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use opentelemetry_sdk::logs::SdkLoggerProvider;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use serde::Deserialize;
+
+use crate::telemetry::api::TelemetryProvider;
+use crate::telemetry::config::TelemetryConfig;
+use crate::telemetry::error::TelemetryError;
+
+/// Boxed future returned by [`DynTelemetryProvider`]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Object-safe form of [`TelemetryProvider`], implemented for every provider
+pub trait DynTelemetryProvider: Send + Sync {
+    fn build_tracer_provider<'a>(
+        &'a self,
+        config: &'a TelemetryConfig,
+    ) -> BoxFuture<'a, Result<SdkTracerProvider, TelemetryError>>;
+
+    fn build_meter_provider<'a>(
+        &'a self,
+        config: &'a TelemetryConfig,
+    ) -> BoxFuture<'a, Result<SdkMeterProvider, TelemetryError>>;
+
+    fn build_logger_provider<'a>(
+        &'a self,
+        config: &'a TelemetryConfig,
+    ) -> BoxFuture<'a, Result<SdkLoggerProvider, TelemetryError>>;
+}
+
+impl<P: TelemetryProvider> DynTelemetryProvider for P {
+    fn build_tracer_provider<'a>(
+        &'a self,
+        config: &'a TelemetryConfig,
+    ) -> BoxFuture<'a, Result<SdkTracerProvider, TelemetryError>> {
+        Box::pin(TelemetryProvider::build_tracer_provider(self, config))
+    }
+
+    fn build_meter_provider<'a>(
+        &'a self,
+        config: &'a TelemetryConfig,
+    ) -> BoxFuture<'a, Result<SdkMeterProvider, TelemetryError>> {
+        Box::pin(TelemetryProvider::build_meter_provider(self, config))
+    }
+
+    fn build_logger_provider<'a>(
+        &'a self,
+        config: &'a TelemetryConfig,
+    ) -> BoxFuture<'a, Result<SdkLoggerProvider, TelemetryError>> {
+        Box::pin(TelemetryProvider::build_logger_provider(self, config))
+    }
+}
+
+/// Named provider for [`TelemetryBackend::Custom`](crate::telemetry::TelemetryBackend::Custom).
+///
+/// In config files this is the name of a registered backend.
+#[derive(Clone, Deserialize)]
+#[serde(try_from = "String")]
+pub struct CustomBackend {
+    name: String,
+    provider: Arc<dyn DynTelemetryProvider>,
+}
+
+impl CustomBackend {
+    pub fn new(name: impl Into<String>, provider: impl TelemetryProvider + 'static) -> Self {
+        Self {
+            name: name.into(),
+            provider: Arc::new(provider),
+        }
+    }
+
+    /// The backend registered as `name`, if any
+    pub fn named(name: &str) -> Option<Self> {
+        registry().read().unwrap().get(name).cloned()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Debug for CustomBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CustomBackend").field(&self.name).finish()
+    }
+}
+
+impl PartialEq for CustomBackend {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && Arc::ptr_eq(&self.provider, &other.provider)
+    }
+}
+
+impl Eq for CustomBackend {}
+
+impl TryFrom<String> for CustomBackend {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        Self::named(&name).ok_or_else(|| {
+            format!(
+                "no telemetry backend registered as {:?} (registered: {})",
+                name,
+                registered_backends().join(", ")
+            )
+        })
+    }
+}
+
+impl TelemetryProvider for CustomBackend {
+    async fn build_tracer_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkTracerProvider, TelemetryError> {
+        self.provider.build_tracer_provider(config).await
+    }
+
+    async fn build_meter_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkMeterProvider, TelemetryError> {
+        self.provider.build_meter_provider(config).await
+    }
+
+    async fn build_logger_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkLoggerProvider, TelemetryError> {
+        self.provider.build_logger_provider(config).await
+    }
+}
+
+fn registry() -> &'static RwLock<HashMap<String, CustomBackend>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, CustomBackend>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Make `provider` selectable by `name`, replacing any backend registered under it
+pub fn register_backend(name: impl Into<String>, provider: impl TelemetryProvider + 'static) {
+    let backend = CustomBackend::new(name, provider);
+    registry()
+        .write()
+        .unwrap()
+        .insert(backend.name.clone(), backend);
+}
+
+/// Names of the registered backends, sorted
+pub fn registered_backends() -> Vec<String> {
+    let mut names: Vec<String> = registry().read().unwrap().keys().cloned().collect();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::config::TelemetryBackend;
+    use crate::telemetry::default::DefaultProvider;
+    use crate::telemetry::testing::InMemoryProvider;
+    use opentelemetry::trace::{Tracer, TracerProvider};
+
+    #[tokio::test]
+    async fn custom_backend_builds_through_dyn_provider() {
+        let test = InMemoryProvider::new();
+        let backend = CustomBackend::new("in-memory", test.clone());
+        let config = TelemetryConfig::new("svc", "1.0");
+
+        let provider = TelemetryProvider::build_tracer_provider(&backend, &config)
+            .await
+            .unwrap();
+        provider.tracer("test").in_span("work", |_| {});
+
+        assert_eq!(test.spans().len(), 1);
+    }
+
+    #[test]
+    fn registered_backend_is_found_by_name() {
+        register_backend("registry-test", DefaultProvider);
+
+        let backend = CustomBackend::named("registry-test").unwrap();
+
+        assert_eq!(backend.name(), "registry-test");
+        assert!(registered_backends().contains(&"registry-test".to_string()));
+        assert!(CustomBackend::named("registry-missing").is_none());
+    }
+
+    #[test]
+    fn custom_backends_compare_by_instance() {
+        let a = CustomBackend::new("same", DefaultProvider);
+        let b = CustomBackend::new("same", DefaultProvider);
+
+        assert_eq!(a, a.clone());
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn config_file_selects_registered_backend() {
+        register_backend("registry-toml", DefaultProvider);
+
+        let config: TelemetryConfig = toml::from_str(
+            r#"
+            [backend]
+            custom = "registry-toml"
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            &config.backend,
+            TelemetryBackend::Custom(backend) if backend.name() == "registry-toml"
+        ));
+
+        let err = toml::from_str::<TelemetryConfig>("backend = { custom = \"registry-nope\" }")
+            .unwrap_err();
+        assert!(err.to_string().contains("registry-nope"));
+    }
+}
@@ -1,8 +1,129 @@
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
 use opentelemetry::KeyValue;
+use opentelemetry_sdk::resource::TelemetryResourceDetector;
 use opentelemetry_sdk::Resource;
-use opentelemetry_semantic_conventions::resource::{SERVICE_NAME, SERVICE_VERSION};
+use opentelemetry_semantic_conventions::resource::{
+    CONTAINER_ID, HOST_ARCH, HOST_NAME, OS_TYPE, PROCESS_EXECUTABLE_NAME, PROCESS_EXECUTABLE_PATH,
+    PROCESS_PID, PROCESS_RUNTIME_NAME, SERVICE_NAME, SERVICE_VERSION,
+};
 
 use crate::telemetry::config::TelemetryConfig;
+use crate::telemetry::otlp::parse_headers;
+
+/// Source of resource attributes, run when the providers are built
+pub trait ResourceDetector: fmt::Debug + Send + Sync {
+    fn detect(&self) -> Vec<KeyValue>;
+}
+
+/// `host.name` and `host.arch`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HostDetector;
+
+impl ResourceDetector for HostDetector {
+    fn detect(&self) -> Vec<KeyValue> {
+        let mut attrs = vec![KeyValue::new(HOST_ARCH, host_arch(std::env::consts::ARCH))];
+        if let Some(name) = hostname::get().ok().and_then(|name| name.into_string().ok()) {
+            attrs.push(KeyValue::new(HOST_NAME, name));
+        }
+        attrs
+    }
+}
+
+/// `process.pid`, `process.executable.{name,path}` and `process.runtime.name`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessDetector;
+
+impl ResourceDetector for ProcessDetector {
+    fn detect(&self) -> Vec<KeyValue> {
+        let mut attrs = vec![
+            KeyValue::new(PROCESS_PID, i64::from(std::process::id())),
+            KeyValue::new(PROCESS_RUNTIME_NAME, "rust"),
+        ];
+        if let Ok(path) = std::env::current_exe() {
+            if let Some(name) = path.file_name() {
+                attrs.push(KeyValue::new(
+                    PROCESS_EXECUTABLE_NAME,
+                    name.to_string_lossy().into_owned(),
+                ));
+            }
+            attrs.push(KeyValue::new(
+                PROCESS_EXECUTABLE_PATH,
+                path.to_string_lossy().into_owned(),
+            ));
+        }
+        attrs
+    }
+}
+
+/// `os.type`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsDetector;
+
+impl ResourceDetector for OsDetector {
+    fn detect(&self) -> Vec<KeyValue> {
+        vec![KeyValue::new(OS_TYPE, os_type(std::env::consts::OS))]
+    }
+}
+
+/// `container.id`, read from the process's cgroup (v1) or mount table (v2)
+#[derive(Debug, Clone)]
+pub struct ContainerDetector {
+    proc_root: PathBuf,
+}
+
+impl Default for ContainerDetector {
+    fn default() -> Self {
+        Self {
+            proc_root: PathBuf::from("/proc"),
+        }
+    }
+}
+
+impl ContainerDetector {
+    /// Read `{root}/self/cgroup` and `{root}/self/mountinfo` instead of `/proc/...`
+    pub fn with_proc_root(root: impl Into<PathBuf>) -> Self {
+        Self {
+            proc_root: root.into(),
+        }
+    }
+}
+
+impl ResourceDetector for ContainerDetector {
+    fn detect(&self) -> Vec<KeyValue> {
+        let read = |file: &str| fs::read_to_string(self.proc_root.join("self").join(file));
+
+        read("cgroup")
+            .ok()
+            .and_then(|cgroup| container_id_from_cgroup(&cgroup))
+            .or_else(|| {
+                read("mountinfo")
+                    .ok()
+                    .and_then(|mountinfo| container_id_from_mountinfo(&mountinfo))
+            })
+            .map(|id| vec![KeyValue::new(CONTAINER_ID, id)])
+            .unwrap_or_default()
+    }
+}
+
+/// Host, process, OS and container detectors
+pub fn default_detectors() -> Vec<Arc<dyn ResourceDetector>> {
+    vec![
+        Arc::new(HostDetector),
+        Arc::new(ProcessDetector),
+        Arc::new(OsDetector),
+        Arc::new(ContainerDetector::default()),
+    ]
+}
+
+/// Parse `OTEL_RESOURCE_ATTRIBUTES` (`key=value,...`, values percent-encoded)
+pub fn parse_resource_attributes(value: &str) -> Vec<(String, String)> {
+    // Same format as the OTLP headers variables
+    parse_headers(value)
+}
 
 /// Get base attributes for any resource
 pub fn base_attributes(config: &TelemetryConfig) -> Vec<KeyValue> {
@@ -14,16 +135,84 @@ pub fn base_attributes(config: &TelemetryConfig) -> Vec<KeyValue> {
 
 /// Build base resource with common attributes
 pub fn build_base_resource(config: &TelemetryConfig) -> Resource {
-    Resource::builder()
-        .with_attributes(base_attributes(config))
-        .build()
+    build_resource(config, Vec::new())
 }
 
-/// Build resource with base + additional attributes
+/// Build resource with base + additional attributes.
+///
+/// When a key comes from several sources, the later source wins:
+///
+/// 1. `telemetry.sdk.*`
+/// 2. `config.resource_detectors`, in order
+/// 3. `additional` (platform attributes, e.g. from the GCP provider)
+/// 4. `config.resource_attributes` (`OTEL_RESOURCE_ATTRIBUTES`)
+/// 5. `service.name` / `service.version` from the config
 pub fn build_resource(config: &TelemetryConfig, additional: Vec<KeyValue>) -> Resource {
-    let mut attrs = base_attributes(config);
+    let mut attrs: Vec<KeyValue> = config
+        .resource_detectors
+        .iter()
+        .flat_map(|detector| detector.detect())
+        .collect();
     attrs.extend(additional);
-    Resource::builder().with_attributes(attrs).build()
+    attrs.extend(
+        config
+            .resource_attributes
+            .iter()
+            .map(|(key, value)| KeyValue::new(key.clone(), value.clone())),
+    );
+    attrs.extend(base_attributes(config));
+
+    Resource::builder_empty()
+        .with_detector(Box::new(TelemetryResourceDetector))
+        .with_attributes(attrs)
+        .build()
+}
+
+/// Semconv `host.arch` value for a Rust target arch
+fn host_arch(arch: &str) -> &str {
+    match arch {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        "arm" => "arm32",
+        "powerpc64" => "ppc64",
+        other => other,
+    }
+}
+
+/// Semconv `os.type` value for a Rust target OS
+fn os_type(os: &str) -> &str {
+    match os {
+        "macos" => "darwin",
+        "dragonfly" => "dragonflybsd",
+        other => other,
+    }
+}
+
+/// Container IDs are 64 hex digits
+fn is_container_id(value: &str) -> bool {
+    value.len() == 64 && value.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Last path segment of a cgroup v1 line, e.g. `/docker/<id>` or
+/// `/kubepods/.../cri-containerd-<id>.scope`
+fn container_id_from_cgroup(cgroup: &str) -> Option<String> {
+    cgroup.lines().find_map(|line| {
+        let segment = line.rsplit('/').next()?;
+        let segment = segment.strip_suffix(".scope").unwrap_or(segment);
+        let id = segment.rsplit('-').next()?;
+        is_container_id(id).then(|| id.to_string())
+    })
+}
+
+/// cgroup v2 hides the ID from `/proc/self/cgroup`; runtimes mount
+/// `.../containers/<id>/hostname` and similar into the container instead
+fn container_id_from_mountinfo(mountinfo: &str) -> Option<String> {
+    mountinfo
+        .lines()
+        .filter(|line| line.contains("/containers/") || line.contains("/sandboxes/"))
+        .flat_map(|line| line.split('/'))
+        .find(|segment| is_container_id(segment))
+        .map(str::to_string)
 }
 
 #[cfg(test)]
@@ -80,4 +269,125 @@ mod tests {
         // Note: We can't easily inspect Resource internals, but we can verify it builds
         assert!(!resource.is_empty());
     }
-}
\ No newline at end of file
+
+    fn value(resource: &Resource, key: &str) -> Option<String> {
+        resource
+            .get(&opentelemetry::Key::from(key.to_string()))
+            .map(|value| value.to_string())
+    }
+
+    /// Detector returning fixed attributes
+    #[derive(Debug)]
+    struct Fixed(Vec<(&'static str, &'static str)>);
+
+    impl ResourceDetector for Fixed {
+        fn detect(&self) -> Vec<KeyValue> {
+            self.0.iter().map(|(k, v)| KeyValue::new(*k, *v)).collect()
+        }
+    }
+
+    #[test]
+    fn build_resource_merges_sources_in_precedence_order() {
+        let config = test_config()
+            .with_resource_detectors(vec![
+                Arc::new(Fixed(vec![
+                    ("host.name", "detected"),
+                    ("cloud.region", "detected"),
+                    ("team", "detected"),
+                ])),
+                Arc::new(Fixed(vec![("host.name", "second-detector")])),
+            ])
+            .with_resource_attribute("team", "payments")
+            .with_resource_attribute("cloud.region", "from-env")
+            .with_resource_attribute("service.name", "ignored");
+
+        let resource = build_resource(&config, vec![KeyValue::new("cloud.region", "platform")]);
+
+        assert_eq!(value(&resource, "host.name").as_deref(), Some("second-detector"));
+        assert_eq!(value(&resource, "cloud.region").as_deref(), Some("from-env"));
+        assert_eq!(value(&resource, "team").as_deref(), Some("payments"));
+        assert_eq!(value(&resource, SERVICE_NAME).as_deref(), Some("test-service"));
+        assert_eq!(value(&resource, "telemetry.sdk.language").as_deref(), Some("rust"));
+    }
+
+    #[test]
+    fn parses_percent_encoded_resource_attributes() {
+        let attrs = parse_resource_attributes(
+            "deployment.environment.name=prod, team=core%20infra,note=a%2Cb%3Dc,broken",
+        );
+
+        assert_eq!(
+            attrs,
+            vec![
+                ("deployment.environment.name".to_string(), "prod".to_string()),
+                ("team".to_string(), "core infra".to_string()),
+                ("note".to_string(), "a,b=c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn built_in_detectors_emit_their_keys() {
+        let keys: Vec<String> = default_detectors()
+            .iter()
+            .flat_map(|detector| detector.detect())
+            .map(|kv| kv.key.to_string())
+            .collect();
+
+        for key in [
+            HOST_NAME,
+            HOST_ARCH,
+            PROCESS_PID,
+            PROCESS_EXECUTABLE_NAME,
+            PROCESS_RUNTIME_NAME,
+            OS_TYPE,
+        ] {
+            assert!(keys.iter().any(|k| k == key), "missing {}", key);
+        }
+    }
+
+    const ID: &str = "3f2a9c6e1b7d4e5f8a0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f";
+
+    #[test]
+    fn reads_container_id_from_cgroup_v1() {
+        let docker = format!("12:memory:/docker/{}\n1:name=systemd:/docker/{}", ID, ID);
+        let containerd = format!(
+            "0::/kubepods.slice/kubepods-burstable.slice/cri-containerd-{}.scope",
+            ID
+        );
+
+        assert_eq!(container_id_from_cgroup(&docker).as_deref(), Some(ID));
+        assert_eq!(container_id_from_cgroup(&containerd).as_deref(), Some(ID));
+        assert_eq!(container_id_from_cgroup("0::/user.slice/session-2.scope"), None);
+    }
+
+    #[test]
+    fn reads_container_id_from_mountinfo() {
+        let mountinfo = format!(
+            "613 600 0:52 / / rw,relatime - overlay overlay rw\n\
+             625 613 254:1 /docker/containers/{}/hostname /etc/hostname rw - ext4 /dev/vda1 rw",
+            ID
+        );
+
+        assert_eq!(container_id_from_mountinfo(&mountinfo).as_deref(), Some(ID));
+    }
+
+    #[test]
+    fn container_detector_uses_proc_root() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir(root.path().join("self")).unwrap();
+        std::fs::write(
+            root.path().join("self/cgroup"),
+            format!("0::/system.slice/docker-{}.scope\n", ID),
+        )
+        .unwrap();
+
+        let attrs = ContainerDetector::with_proc_root(root.path()).detect();
+        assert_eq!(attrs, vec![KeyValue::new(CONTAINER_ID, ID)]);
+
+        let empty = tempfile::tempdir().unwrap();
+        assert!(ContainerDetector::with_proc_root(empty.path())
+            .detect()
+            .is_empty());
+    }
+}
@@ -0,0 +1,209 @@
+use std::env;
+
+use opentelemetry_sdk::trace::Sampler;
+use serde::Deserialize;
+
+/// Trace sampling strategy (mirrors the `OTEL_TRACES_SAMPLER` values).
+///
+/// In config files either a name (`sampler = "always_on"`) or a name with a
+/// ratio (`sampler = { name = "traceidratio", ratio = 0.1 }`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(try_from = "SamplerSpec")]
+pub enum SamplerConfig {
+    /// Sample every trace
+    AlwaysOn,
+    /// Drop every trace
+    AlwaysOff,
+    /// Sample this fraction of traces, by trace ID
+    TraceIdRatio(f64),
+    /// Follow the parent's decision; sample every root span
+    #[default]
+    ParentBasedAlwaysOn,
+    /// Follow the parent's decision; drop every root span
+    ParentBasedAlwaysOff,
+    /// Follow the parent's decision; sample this fraction of root spans
+    ParentBasedTraceIdRatio(f64),
+}
+
+impl SamplerConfig {
+    /// Read `OTEL_TRACES_SAMPLER` / `OTEL_TRACES_SAMPLER_ARG`.
+    ///
+    /// Unknown sampler names fall back to the default (`parentbased_always_on`)
+    /// and a missing or invalid ratio falls back to `1.0`, as the spec requires.
+    pub fn from_env() -> Self {
+        Self::from_env_if_set().unwrap_or_default()
+    }
+
+    /// Parse a sampler name and its optional ratio argument
+    pub fn parse(name: &str, arg: Option<&str>) -> Option<Self> {
+        let ratio = || {
+            arg.and_then(|arg| arg.trim().parse::<f64>().ok())
+                .filter(|ratio| (0.0..=1.0).contains(ratio))
+                .unwrap_or(1.0)
+        };
+
+        match name.trim() {
+            "always_on" => Some(Self::AlwaysOn),
+            "always_off" => Some(Self::AlwaysOff),
+            "traceidratio" => Some(Self::TraceIdRatio(ratio())),
+            "parentbased_always_on" => Some(Self::ParentBasedAlwaysOn),
+            "parentbased_always_off" => Some(Self::ParentBasedAlwaysOff),
+            "parentbased_traceidratio" => Some(Self::ParentBasedTraceIdRatio(ratio())),
+            _ => None,
+        }
+    }
+
+    /// Sampler named by `OTEL_TRACES_SAMPLER`, if set to a known name
+    pub fn from_env_if_set() -> Option<Self> {
+        let arg = env::var("OTEL_TRACES_SAMPLER_ARG").ok();
+        env::var("OTEL_TRACES_SAMPLER")
+            .ok()
+            .and_then(|name| Self::parse(&name, arg.as_deref()))
+    }
+
+    /// Fraction of root spans kept
+    pub fn root_ratio(self) -> f64 {
+        match self {
+            Self::AlwaysOn | Self::ParentBasedAlwaysOn => 1.0,
+            Self::AlwaysOff | Self::ParentBasedAlwaysOff => 0.0,
+            Self::TraceIdRatio(ratio) | Self::ParentBasedTraceIdRatio(ratio) => ratio,
+        }
+    }
+
+    /// Build the SDK sampler
+    pub fn to_sampler(self) -> Sampler {
+        match self {
+            Self::AlwaysOn => Sampler::AlwaysOn,
+            Self::AlwaysOff => Sampler::AlwaysOff,
+            Self::TraceIdRatio(ratio) => Sampler::TraceIdRatioBased(ratio),
+            Self::ParentBasedAlwaysOn => Sampler::ParentBased(Box::new(Sampler::AlwaysOn)),
+            Self::ParentBasedAlwaysOff => Sampler::ParentBased(Box::new(Sampler::AlwaysOff)),
+            Self::ParentBasedTraceIdRatio(ratio) => {
+                Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(ratio)))
+            }
+        }
+    }
+}
+
+/// Config file form of [`SamplerConfig`]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SamplerSpec {
+    Name(String),
+    WithRatio { name: String, ratio: f64 },
+}
+
+impl TryFrom<SamplerSpec> for SamplerConfig {
+    type Error = String;
+
+    fn try_from(spec: SamplerSpec) -> Result<Self, Self::Error> {
+        let (name, ratio) = match spec {
+            SamplerSpec::Name(name) => (name, None),
+            SamplerSpec::WithRatio { name, ratio } => (name, Some(ratio)),
+        };
+
+        let sampler = match (name.trim(), ratio) {
+            ("traceidratio", Some(ratio)) => Some(Self::TraceIdRatio(ratio)),
+            ("parentbased_traceidratio", Some(ratio)) => Some(Self::ParentBasedTraceIdRatio(ratio)),
+            (name, _) => Self::parse(name, None),
+        };
+        sampler.ok_or_else(|| format!("unknown sampler {:?}", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::testing::EnvGuard;
+    use opentelemetry::trace::{Tracer, TracerProvider};
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+
+    fn exported_spans(sampler: SamplerConfig) -> usize {
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .with_sampler(sampler.to_sampler())
+            .build();
+
+        let tracer = provider.tracer("test");
+        for _ in 0..10 {
+            tracer.in_span("work", |_| {});
+        }
+
+        exporter.get_finished_spans().unwrap().len()
+    }
+
+    #[test]
+    fn default_is_parent_based_always_on() {
+        assert_eq!(SamplerConfig::default(), SamplerConfig::ParentBasedAlwaysOn);
+    }
+
+    #[test]
+    fn parses_every_sampler_name() {
+        let cases = [
+            ("always_on", SamplerConfig::AlwaysOn),
+            ("always_off", SamplerConfig::AlwaysOff),
+            ("traceidratio", SamplerConfig::TraceIdRatio(0.25)),
+            ("parentbased_always_on", SamplerConfig::ParentBasedAlwaysOn),
+            (
+                "parentbased_always_off",
+                SamplerConfig::ParentBasedAlwaysOff,
+            ),
+            (
+                "parentbased_traceidratio",
+                SamplerConfig::ParentBasedTraceIdRatio(0.25),
+            ),
+        ];
+
+        for (name, expected) in cases {
+            assert_eq!(SamplerConfig::parse(name, Some("0.25")), Some(expected));
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_sampler() {
+        assert_eq!(SamplerConfig::parse("jaeger_remote", None), None);
+    }
+
+    #[test]
+    fn invalid_ratio_falls_back_to_one() {
+        assert_eq!(
+            SamplerConfig::parse("traceidratio", None),
+            Some(SamplerConfig::TraceIdRatio(1.0))
+        );
+        assert_eq!(
+            SamplerConfig::parse("traceidratio", Some("abc")),
+            Some(SamplerConfig::TraceIdRatio(1.0))
+        );
+        assert_eq!(
+            SamplerConfig::parse("traceidratio", Some("1.5")),
+            Some(SamplerConfig::TraceIdRatio(1.0))
+        );
+    }
+
+    #[test]
+    fn from_env_reads_sampler_and_arg() {
+        let _guard = EnvGuard::new(&["OTEL_TRACES_SAMPLER", "OTEL_TRACES_SAMPLER_ARG"]);
+        env::set_var("OTEL_TRACES_SAMPLER", "parentbased_traceidratio");
+        env::set_var("OTEL_TRACES_SAMPLER_ARG", "0.1");
+
+        let sampler = SamplerConfig::from_env();
+
+        assert_eq!(sampler, SamplerConfig::ParentBasedTraceIdRatio(0.1));
+    }
+
+    #[test]
+    fn always_off_drops_spans() {
+        assert_eq!(exported_spans(SamplerConfig::AlwaysOff), 0);
+    }
+
+    #[test]
+    fn always_on_keeps_spans() {
+        assert_eq!(exported_spans(SamplerConfig::AlwaysOn), 10);
+    }
+
+    #[test]
+    fn zero_ratio_drops_spans() {
+        assert_eq!(exported_spans(SamplerConfig::TraceIdRatio(0.0)), 0);
+    }
+}
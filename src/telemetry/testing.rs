@@ -0,0 +1,317 @@
+//! In-memory telemetry capture for tests.
+//!
+//! [`InMemoryProvider`] is a [`TelemetryProvider`] whose exporters keep
+//! finished spans and log records in memory. Its [`subscriber`](InMemoryProvider::subscriber)
+//! wires the same layers as production (OpenTelemetry tracing layer and log
+//! bridge), so tests see the spans and attributes handlers really produce.
+//!
+//! ```rust,ignore
+//! let telemetry = InMemoryProvider::new();
+//! let _guard = telemetry.set_default();
+//!
+//! let app = init_service(App::new().wrap(TracingLogger::default()).service(hello)).await;
+//! call_service(&app, TestRequest::get().uri("/?user=ada").to_request()).await;
+//!
+//! let handler = telemetry.span("hello");
+//! assert_attribute(&handler, "user", "ada");
+//! assert_parent(&handler, &telemetry.span("GET /"));
+//! ```
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use opentelemetry::logs::AnyValue;
+use opentelemetry::trace::SpanId;
+use opentelemetry::{Key, Value};
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::logs::{LogBatch, LogExporter, SdkLogRecord, SdkLoggerProvider};
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::trace::{SdkTracerProvider, SpanData, SpanExporter};
+use tracing::subscriber::DefaultGuard;
+use tracing::Subscriber;
+use tracing_subscriber::layer::SubscriberExt;
+
+use crate::telemetry::api::TelemetryProvider;
+use crate::telemetry::config::TelemetryConfig;
+use crate::telemetry::error::TelemetryError;
+use crate::telemetry::logs::build_log_bridge_layer;
+use crate::telemetry::resource::build_base_resource;
+use crate::telemetry::trace::build_otel_layer;
+
+/// Telemetry provider capturing spans and logs in memory.
+///
+/// Clones share the captured data. Spans and records are exported as soon as
+/// they end, so no flush is needed before asserting, and they are kept when the
+/// providers shut down.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryProvider {
+    spans: SpanStore,
+    logs: LogStore,
+}
+
+impl InMemoryProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn tracer_provider(&self) -> SdkTracerProvider {
+        SdkTracerProvider::builder()
+            .with_simple_exporter(self.spans.clone())
+            .build()
+    }
+
+    fn logger_provider(&self) -> SdkLoggerProvider {
+        SdkLoggerProvider::builder()
+            .with_simple_exporter(self.logs.clone())
+            .build()
+    }
+
+    /// Subscriber recording every span and event into this provider
+    pub fn subscriber(&self) -> impl Subscriber + Send + Sync {
+        tracing_subscriber::registry()
+            .with(build_otel_layer(&self.tracer_provider(), "test"))
+            .with(build_log_bridge_layer(&self.logger_provider()))
+    }
+
+    /// Install [`subscriber`](Self::subscriber) for the current thread until the guard drops
+    pub fn set_default(&self) -> DefaultGuard {
+        tracing::subscriber::set_default(self.subscriber())
+    }
+
+    /// Finished spans, in the order they ended
+    pub fn spans(&self) -> Vec<SpanData> {
+        self.spans.0.lock().unwrap().clone()
+    }
+
+    /// The finished span named `name`.
+    ///
+    /// Panics, listing the captured span names, if there is none.
+    pub fn span(&self, name: &str) -> SpanData {
+        let spans = self.spans();
+        spans
+            .iter()
+            .find(|span| span.name == name)
+            .cloned()
+            .unwrap_or_else(|| {
+                let names: Vec<_> = spans.iter().map(|span| span.name.as_ref()).collect();
+                panic!("no span named {:?}; captured spans: {:?}", name, names)
+            })
+    }
+
+    /// Emitted log records, in order
+    pub fn logs(&self) -> Vec<SdkLogRecord> {
+        self.logs.0.lock().unwrap().clone()
+    }
+
+    /// Bodies of the emitted log records (the event messages)
+    pub fn log_messages(&self) -> Vec<String> {
+        self.logs()
+            .iter()
+            .filter_map(|record| match record.body() {
+                Some(AnyValue::String(message)) => Some(message.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Discard everything captured so far
+    pub fn reset(&self) {
+        self.spans.0.lock().unwrap().clear();
+        self.logs.0.lock().unwrap().clear();
+    }
+}
+
+/// Span exporter appending to a shared list; shutdown keeps the spans
+#[derive(Debug, Clone, Default)]
+struct SpanStore(Arc<Mutex<Vec<SpanData>>>);
+
+impl SpanExporter for SpanStore {
+    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        self.0.lock().unwrap().extend(batch);
+        Ok(())
+    }
+}
+
+/// Log exporter appending to a shared list; shutdown keeps the records
+#[derive(Debug, Clone, Default)]
+struct LogStore(Arc<Mutex<Vec<SdkLogRecord>>>);
+
+impl LogExporter for LogStore {
+    async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
+        let records = batch.iter().map(|(record, _)| record.clone());
+        self.0.lock().unwrap().extend(records);
+        Ok(())
+    }
+}
+
+impl TelemetryProvider for InMemoryProvider {
+    async fn build_tracer_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkTracerProvider, TelemetryError> {
+        Ok(SdkTracerProvider::builder()
+            .with_simple_exporter(self.spans.clone())
+            .with_sampler(config.sampler.to_sampler())
+            .with_resource(build_base_resource(config))
+            .build())
+    }
+
+    async fn build_meter_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkMeterProvider, TelemetryError> {
+        Ok(SdkMeterProvider::builder()
+            .with_resource(build_base_resource(config))
+            .build())
+    }
+
+    async fn build_logger_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkLoggerProvider, TelemetryError> {
+        Ok(SdkLoggerProvider::builder()
+            .with_simple_exporter(self.logs.clone())
+            .with_resource(build_base_resource(config))
+            .build())
+    }
+}
+
+/// Value of the attribute `key` on `span`
+pub fn attribute<'a>(span: &'a SpanData, key: &str) -> Option<&'a Value> {
+    span.attributes
+        .iter()
+        .find(|attribute| attribute.key == Key::from(key.to_string()))
+        .map(|attribute| &attribute.value)
+}
+
+/// Assert that `span` has the attribute `key` set to `expected`
+#[track_caller]
+pub fn assert_attribute(span: &SpanData, key: &str, expected: impl Into<Value>) {
+    let expected = expected.into();
+    match attribute(span, key) {
+        Some(actual) => assert_eq!(
+            actual, &expected,
+            "attribute {:?} of span {:?}",
+            key, span.name
+        ),
+        None => panic!(
+            "span {:?} has no attribute {:?}; attributes: {:?}",
+            span.name, key, span.attributes
+        ),
+    }
+}
+
+/// Assert that `child` is a direct child of `parent` in the same trace
+#[track_caller]
+pub fn assert_parent(child: &SpanData, parent: &SpanData) {
+    assert_eq!(
+        child.span_context.trace_id(),
+        parent.span_context.trace_id(),
+        "span {:?} is not in the trace of {:?}",
+        child.name,
+        parent.name
+    );
+    assert_eq!(
+        child.parent_span_id,
+        parent.span_context.span_id(),
+        "span {:?} is not a child of {:?}",
+        child.name,
+        parent.name
+    );
+}
+
+/// Assert that `span` has no parent
+#[track_caller]
+pub fn assert_root(span: &SpanData) {
+    assert_eq!(
+        span.parent_span_id,
+        SpanId::INVALID,
+        "span {:?} is not a root span",
+        span.name
+    );
+}
+
+/// Serializes tests that change environment variables
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Removes the listed environment variables when dropped, so a test's
+/// variables don't leak into later tests.
+///
+/// Holds a process-wide lock meanwhile, so tests using it never see each
+/// other's variables. Take one guard per test: a second one deadlocks.
+pub struct EnvGuard {
+    vars: Vec<&'static str>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl EnvGuard {
+    pub fn new(vars: &[&'static str]) -> Self {
+        // A test that panicked while holding the lock still removed its variables
+        let lock = ENV_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        Self {
+            vars: vars.to_vec(),
+            _lock: lock,
+        }
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        for var in &self.vars {
+            std::env::remove_var(var);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_span_tree_and_events() {
+        let telemetry = InMemoryProvider::new();
+
+        tracing::subscriber::with_default(telemetry.subscriber(), || {
+            let request = tracing::info_span!("request", http.method = "GET");
+            let _entered = request.enter();
+            tracing::info_span!("query", rows = 3).in_scope(|| {
+                tracing::info!("query done");
+            });
+        });
+
+        let request = telemetry.span("request");
+        let query = telemetry.span("query");
+        assert_root(&request);
+        assert_parent(&query, &request);
+        assert_attribute(&request, "http.method", "GET");
+        assert_attribute(&query, "rows", 3_i64);
+        assert_eq!(telemetry.log_messages(), vec!["query done"]);
+
+        telemetry.reset();
+        assert!(telemetry.spans().is_empty());
+        assert!(telemetry.logs().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "captured spans: [\"request\"]")]
+    fn span_lists_captured_names_when_missing() {
+        let telemetry = InMemoryProvider::new();
+        tracing::subscriber::with_default(telemetry.subscriber(), || {
+            tracing::info_span!("request").in_scope(|| {});
+        });
+
+        telemetry.span("missing");
+    }
+
+    #[tokio::test]
+    async fn provider_exports_into_shared_storage() {
+        use opentelemetry::trace::{Tracer, TracerProvider};
+
+        let telemetry = InMemoryProvider::new();
+        let config = TelemetryConfig::new("test-service", "1.0.0");
+
+        let provider = telemetry.build_tracer_provider(&config).await.unwrap();
+        provider.tracer("test").in_span("work", |_| {});
+
+        assert_eq!(telemetry.spans().len(), 1);
+    }
+}
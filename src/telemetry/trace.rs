@@ -1,4 +1,5 @@
 use opentelemetry::trace::TracerProvider;
+use opentelemetry_sdk::logs::SdkLoggerProvider;
 use opentelemetry_sdk::trace::SdkTracerProvider;
 use tracing::Subscriber;
 use tracing_opentelemetry::OpenTelemetryLayer;
@@ -8,7 +9,9 @@ use tracing_subscriber::registry::LookupSpan;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{EnvFilter, Layer};
 
-use crate::telemetry::config::{LogFormat, TelemetryConfig};
+use crate::telemetry::config::{LogFormat, TelemetryBackend, TelemetryConfig};
+use crate::telemetry::log_level::LogLevelHandle;
+use crate::telemetry::logs::build_log_bridge_layer;
 
 /// Build the OpenTelemetry tracing layer
 pub fn build_otel_layer<S>(
@@ -57,11 +60,19 @@ pub fn build_filter(config: &TelemetryConfig) -> EnvFilter {
 }
 
 /// Initialize the global tracing subscriber with all layers
-pub fn init_subscriber(provider: SdkTracerProvider, config: &TelemetryConfig) {
+///
+/// When a logger provider is given, events are also bridged to OpenTelemetry logs.
+/// The returned handle changes the log filter at runtime.
+pub fn init_subscriber(
+    provider: SdkTracerProvider,
+    logger_provider: Option<&SdkLoggerProvider>,
+    config: &TelemetryConfig,
+) -> LogLevelHandle {
     opentelemetry::global::set_tracer_provider(provider.clone());
 
     let otel_layer = build_otel_layer(&provider, &config.service_name);
-    let filter = build_filter(config);
+    let log_layer = logger_provider.map(build_log_bridge_layer);
+    let (filter, log_level) = LogLevelHandle::reloadable(build_filter(config));
 
     match config.log_format {
         LogFormat::Pretty => {
@@ -69,6 +80,7 @@ pub fn init_subscriber(provider: SdkTracerProvider, config: &TelemetryConfig) {
             tracing_subscriber::registry()
                 .with(filter)
                 .with(otel_layer)
+                .with(log_layer)
                 .with(fmt_layer)
                 .init();
         }
@@ -77,9 +89,32 @@ pub fn init_subscriber(provider: SdkTracerProvider, config: &TelemetryConfig) {
             tracing_subscriber::registry()
                 .with(filter)
                 .with(otel_layer)
+                .with(log_layer)
                 .with(fmt_layer)
                 .init();
         }
+        #[cfg(feature = "telemetry-gcp")]
+        LogFormat::GcpJson => {
+            let fmt_layer =
+                crate::telemetry::gcp::logging::build_gcp_json_layer(gcp_project_id(config));
+            tracing_subscriber::registry()
+                .with(filter)
+                .with(otel_layer)
+                .with(log_layer)
+                .with(fmt_layer)
+                .init();
+        }
+    }
+
+    log_level
+}
+
+/// Project used to link log entries to Cloud Trace, if the GCP backend is active
+#[cfg(feature = "telemetry-gcp")]
+fn gcp_project_id(config: &TelemetryConfig) -> Option<String> {
+    match &config.backend {
+        TelemetryBackend::Gcp(gcp_config) => Some(gcp_config.project_id.clone()),
+        _ => None,
     }
 }
 
@@ -0,0 +1,76 @@
+use std::env;
+
+use serde::Deserialize;
+
+use crate::telemetry::config::check_endpoint;
+use crate::telemetry::error::ConfigProblem;
+
+/// Span collection endpoint of a Zipkin server on this machine
+pub const DEFAULT_ENDPOINT: &str = "http://localhost:9411/api/v2/spans";
+
+/// Zipkin-specific configuration
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ZipkinConfig {
+    /// Full URL spans are POSTed to, including `/api/v2/spans`
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+}
+
+fn default_endpoint() -> String {
+    DEFAULT_ENDPOINT.to_string()
+}
+
+impl Default for ZipkinConfig {
+    fn default() -> Self {
+        Self::new(DEFAULT_ENDPOINT)
+    }
+}
+
+impl ZipkinConfig {
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+        }
+    }
+
+    /// Record problems with the endpoint
+    pub fn validate(&self, problems: &mut Vec<ConfigProblem>) {
+        check_endpoint("zipkin.endpoint", &self.endpoint, problems);
+    }
+
+    /// Create from `OTEL_EXPORTER_ZIPKIN_ENDPOINT`, if set
+    pub fn from_env() -> Option<Self> {
+        env::var("OTEL_EXPORTER_ZIPKIN_ENDPOINT")
+            .ok()
+            .map(Self::new)
+    }
+
+    /// Replace the endpoint with `OTEL_EXPORTER_ZIPKIN_ENDPOINT`, if set
+    pub fn with_env_overrides(self) -> Self {
+        Self::from_env().unwrap_or(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zipkin_config_default_uses_local_server() {
+        assert_eq!(ZipkinConfig::default().endpoint, DEFAULT_ENDPOINT);
+    }
+
+    #[test]
+    fn validate_reports_endpoint() {
+        let mut problems = Vec::new();
+        ZipkinConfig::new("zipkin:9411").validate(&mut problems);
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].field, "zipkin.endpoint");
+
+        let mut problems = Vec::new();
+        ZipkinConfig::default().validate(&mut problems);
+        assert!(problems.is_empty());
+    }
+}
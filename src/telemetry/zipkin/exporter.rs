@@ -0,0 +1,277 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use opentelemetry::trace::{SpanId, SpanKind, Status};
+use opentelemetry::Key;
+use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
+use opentelemetry_sdk::trace::{SpanData, SpanExporter};
+use opentelemetry_sdk::Resource;
+use opentelemetry_semantic_conventions::resource::SERVICE_NAME;
+use serde::Serialize;
+
+use crate::telemetry::error::TelemetryError;
+
+/// Upper bound for one POST (the `OTEL_EXPORTER_ZIPKIN_TIMEOUT` default)
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Span exporter POSTing batches to a Zipkin server as JSON v2.
+///
+/// Sends from the batch processor's export thread with a blocking client.
+pub struct ZipkinExporter {
+    endpoint: String,
+    client: reqwest::blocking::Client,
+    service_name: String,
+}
+
+impl ZipkinExporter {
+    /// Exporter for a span collection URL, e.g. `http://zipkin:9411/api/v2/spans`
+    pub fn new(endpoint: impl Into<String>) -> Result<Self, TelemetryError> {
+        // The blocking client panics if built on an async runtime thread
+        let client = std::thread::spawn(|| {
+            reqwest::blocking::Client::builder()
+                .timeout(DEFAULT_TIMEOUT)
+                .build()
+        })
+        .join()
+        .map_err(|_| TelemetryError::Exporter("Zipkin client thread panicked".into()))?
+        .map_err(|e| TelemetryError::Exporter(format!("Failed to build Zipkin client: {}", e)))?;
+
+        Ok(Self {
+            endpoint: endpoint.into(),
+            client,
+            service_name: String::new(),
+        })
+    }
+}
+
+impl fmt::Debug for ZipkinExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZipkinExporter")
+            .field("endpoint", &self.endpoint)
+            .field("service_name", &self.service_name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SpanExporter for ZipkinExporter {
+    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        let spans: Vec<ZipkinSpan> = batch
+            .iter()
+            .map(|span| ZipkinSpan::new(span, &self.service_name))
+            .collect();
+        let body =
+            serde_json::to_vec(&spans).map_err(|e| OTelSdkError::InternalFailure(e.to_string()))?;
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header("content-type", "application/json")
+            .body(body)
+            .send()
+            .map_err(|e| OTelSdkError::InternalFailure(e.to_string()))?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(OTelSdkError::InternalFailure(format!(
+                "Zipkin responded with {}",
+                response.status()
+            )))
+        }
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        if let Some(service_name) = resource.get(&Key::from_static_str(SERVICE_NAME)) {
+            self.service_name = service_name.to_string();
+        }
+    }
+}
+
+/// One span in the Zipkin JSON v2 format
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ZipkinSpan {
+    trace_id: String,
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<String>,
+    name: String,
+    /// Omitted for internal spans
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<&'static str>,
+    /// Microseconds since the epoch
+    timestamp: u64,
+    duration: u64,
+    local_endpoint: Endpoint,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<Annotation>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    tags: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Endpoint {
+    service_name: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Annotation {
+    timestamp: u64,
+    value: String,
+}
+
+impl ZipkinSpan {
+    pub(crate) fn new(span: &SpanData, service_name: &str) -> Self {
+        let mut tags: BTreeMap<String, String> = span
+            .attributes
+            .iter()
+            .map(|kv| (kv.key.to_string(), kv.value.to_string()))
+            .collect();
+        tags.insert(
+            "otel.scope.name".to_string(),
+            span.instrumentation_scope.name().to_string(),
+        );
+        match &span.status {
+            Status::Unset => {}
+            Status::Ok => {
+                tags.insert("otel.status_code".to_string(), "OK".to_string());
+            }
+            Status::Error { description } => {
+                tags.insert("otel.status_code".to_string(), "ERROR".to_string());
+                tags.insert("error".to_string(), description.to_string());
+            }
+        }
+
+        let timestamp = micros_since_epoch(span.start_time);
+        Self {
+            trace_id: span.span_context.trace_id().to_string(),
+            id: span.span_context.span_id().to_string(),
+            parent_id: (span.parent_span_id != SpanId::INVALID)
+                .then(|| span.parent_span_id.to_string()),
+            name: span.name.to_string(),
+            kind: match span.span_kind {
+                SpanKind::Server => Some("SERVER"),
+                SpanKind::Client => Some("CLIENT"),
+                SpanKind::Producer => Some("PRODUCER"),
+                SpanKind::Consumer => Some("CONSUMER"),
+                SpanKind::Internal => None,
+            },
+            timestamp,
+            // Zipkin treats 0 as "unknown"
+            duration: micros_since_epoch(span.end_time)
+                .saturating_sub(timestamp)
+                .max(1),
+            local_endpoint: Endpoint {
+                service_name: service_name.to_string(),
+            },
+            annotations: span
+                .events
+                .iter()
+                .map(|event| Annotation {
+                    timestamp: micros_since_epoch(event.timestamp),
+                    value: event.name.to_string(),
+                })
+                .collect(),
+            tags,
+        }
+    }
+}
+
+fn micros_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_micros() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{SpanContext, TraceFlags, TraceId, TraceState};
+    use opentelemetry::{InstrumentationScope, KeyValue};
+    use opentelemetry_sdk::trace::{SpanEvents, SpanLinks};
+    use std::borrow::Cow;
+
+    fn span_data(kind: SpanKind, status: Status) -> SpanData {
+        let start = UNIX_EPOCH + Duration::from_micros(1_700_000_000_000_000);
+        let mut events = SpanEvents::default();
+        events.events.push(opentelemetry::trace::Event::new(
+            "cache miss",
+            start + Duration::from_micros(250),
+            vec![],
+            0,
+        ));
+
+        SpanData {
+            span_context: SpanContext::new(
+                TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap(),
+                SpanId::from_hex("00f067aa0ba902b7").unwrap(),
+                TraceFlags::SAMPLED,
+                false,
+                TraceState::default(),
+            ),
+            parent_span_id: SpanId::from_hex("53995c3f42cd8ad8").unwrap(),
+            parent_span_is_remote: false,
+            span_kind: kind,
+            name: Cow::Borrowed("GET /orders"),
+            start_time: start,
+            end_time: start + Duration::from_micros(1_500),
+            attributes: vec![KeyValue::new("http.request.method", "GET")],
+            dropped_attributes_count: 0,
+            events,
+            links: SpanLinks::default(),
+            status,
+            instrumentation_scope: InstrumentationScope::builder("orders-api").build(),
+        }
+    }
+
+    #[test]
+    fn serializes_json_v2_span() {
+        let span = ZipkinSpan::new(&span_data(SpanKind::Server, Status::Ok), "orders");
+
+        assert_eq!(
+            serde_json::to_value(&span).unwrap(),
+            serde_json::json!({
+                "traceId": "4bf92f3577b34da6a3ce929d0e0e4736",
+                "id": "00f067aa0ba902b7",
+                "parentId": "53995c3f42cd8ad8",
+                "name": "GET /orders",
+                "kind": "SERVER",
+                "timestamp": 1_700_000_000_000_000u64,
+                "duration": 1_500,
+                "localEndpoint": { "serviceName": "orders" },
+                "annotations": [
+                    { "timestamp": 1_700_000_000_000_250u64, "value": "cache miss" }
+                ],
+                "tags": {
+                    "http.request.method": "GET",
+                    "otel.scope.name": "orders-api",
+                    "otel.status_code": "OK"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn internal_root_span_omits_kind_and_parent() {
+        let mut data = span_data(SpanKind::Internal, Status::Unset);
+        data.parent_span_id = SpanId::INVALID;
+
+        let span = serde_json::to_value(ZipkinSpan::new(&data, "orders")).unwrap();
+
+        assert!(span.get("kind").is_none());
+        assert!(span.get("parentId").is_none());
+        assert!(span["tags"].get("otel.status_code").is_none());
+    }
+
+    #[test]
+    fn error_status_becomes_error_tag() {
+        let data = span_data(SpanKind::Client, Status::error("connection refused"));
+
+        let span = serde_json::to_value(ZipkinSpan::new(&data, "orders")).unwrap();
+
+        assert_eq!(span["kind"], "CLIENT");
+        assert_eq!(span["tags"]["otel.status_code"], "ERROR");
+        assert_eq!(span["tags"]["error"], "connection refused");
+    }
+}
@@ -0,0 +1,195 @@
+//! Zipkin telemetry provider.
+//!
+//! This module exports traces to a Zipkin server in its JSON v2 format and
+//! handles B3 context propagation, for infrastructure that predates OTLP.
+//!
+//! # Features
+//!
+//! - Spans POSTed to `/api/v2/spans` from the batch processor's export thread
+//! - B3 propagation ([`B3Propagator`]): both the single `b3` header and the
+//!   `X-B3-*` headers are read; `OTEL_PROPAGATORS` picks which are written
+//!
+//! Zipkin only stores traces, so metrics and logs go wherever the default
+//! provider would send them: OTLP to `otlp_endpoint` if set, otherwise nowhere.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use telemetry::zipkin::ZipkinConfig;
+//!
+//! let config = TelemetryConfig::builder()
+//!     .backend(TelemetryBackend::Zipkin(ZipkinConfig::new(
+//!         "http://zipkin.internal:9411/api/v2/spans",
+//!     )))
+//!     .build();
+//! ```
+//!
+//! # Environment Variables
+//!
+//! - `OTEL_EXPORTER_ZIPKIN_ENDPOINT`: Span collection URL; setting it selects this backend
+//! - `OTEL_PROPAGATORS`: `b3` writes the single header, `b3multi` (default)
+//!   the `X-B3-*` headers, both write both
+
+pub mod config;
+pub mod exporter;
+pub mod propagator;
+
+use opentelemetry_sdk::logs::SdkLoggerProvider;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+
+use crate::telemetry::api::TelemetryProvider;
+use crate::telemetry::config::TelemetryConfig;
+use crate::telemetry::default::DefaultProvider;
+use crate::telemetry::error::TelemetryError;
+use crate::telemetry::fanout::with_secondary_backends;
+use crate::telemetry::resource::build_base_resource;
+
+pub use config::ZipkinConfig;
+pub use exporter::ZipkinExporter;
+pub use propagator::{B3Encoding, B3Propagator};
+
+/// Zipkin telemetry provider.
+///
+/// Exports traces to `ZipkinConfig::endpoint`; metrics and logs as
+/// [`DefaultProvider`] does.
+pub struct ZipkinProvider {
+    config: ZipkinConfig,
+}
+
+impl ZipkinProvider {
+    pub fn new(config: ZipkinConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl TelemetryProvider for ZipkinProvider {
+    async fn build_tracer_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkTracerProvider, TelemetryError> {
+        let exporter = ZipkinExporter::new(&self.config.endpoint)?;
+
+        let builder = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_sampler(config.sampler.to_sampler())
+            .with_resource(build_base_resource(config));
+
+        Ok(with_secondary_backends(builder, config).await.build())
+    }
+
+    async fn build_meter_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkMeterProvider, TelemetryError> {
+        DefaultProvider.build_meter_provider(config).await
+    }
+
+    async fn build_logger_provider(
+        &self,
+        config: &TelemetryConfig,
+    ) -> Result<SdkLoggerProvider, TelemetryError> {
+        DefaultProvider.build_logger_provider(config).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{Span, SpanKind, Status, Tracer, TracerProvider};
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Stand-in for a Zipkin server's collection endpoint
+    async fn zipkin_stub() -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v2/spans"))
+            .and(header("content-type", "application/json"))
+            .respond_with(ResponseTemplate::new(202))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    /// Flush spans to the stub and return the JSON array it received
+    async fn flush(provider: SdkTracerProvider, server: &MockServer) -> serde_json::Value {
+        // The exporter blocks, so flush off the runtime's worker threads
+        tokio::task::spawn_blocking(move || provider.force_flush())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        serde_json::from_slice(&requests[0].body).unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn zipkin_provider_posts_json_v2_spans() {
+        let server = zipkin_stub().await;
+        let provider =
+            ZipkinProvider::new(ZipkinConfig::new(format!("{}/api/v2/spans", server.uri())));
+        let config = TelemetryConfig::new("orders", "1.0.0");
+
+        let tracer_provider = provider.build_tracer_provider(&config).await.unwrap();
+        let tracer = tracer_provider.tracer("orders-api");
+        tracer.in_span("checkout", |_| {
+            let mut span = tracer
+                .span_builder("charge card")
+                .with_kind(SpanKind::Client)
+                .start(&tracer);
+            span.set_attribute(opentelemetry::KeyValue::new("payment.provider", "acme"));
+            span.add_event("retry", vec![]);
+            span.set_status(Status::error("declined"));
+            span.end();
+        });
+
+        let body = flush(tracer_provider, &server).await;
+        let spans = body.as_array().unwrap();
+        assert_eq!(spans.len(), 2);
+        let (child, parent) = (&spans[0], &spans[1]);
+
+        assert_eq!(parent["name"], "checkout");
+        assert!(parent.get("parentId").is_none());
+        assert!(parent.get("kind").is_none());
+        assert_eq!(parent["localEndpoint"]["serviceName"], "orders");
+
+        assert_eq!(child["name"], "charge card");
+        assert_eq!(child["kind"], "CLIENT");
+        assert_eq!(child["traceId"], parent["traceId"]);
+        assert_eq!(child["parentId"], parent["id"]);
+        assert_eq!(child["tags"]["payment.provider"], "acme");
+        assert_eq!(child["tags"]["error"], "declined");
+        assert_eq!(child["annotations"][0]["value"], "retry");
+        assert_eq!(child["traceId"].as_str().unwrap().len(), 32);
+        assert_eq!(child["id"].as_str().unwrap().len(), 16);
+        assert!(child["timestamp"].as_u64().unwrap() > 0);
+        assert!(child["duration"].as_u64().unwrap() >= 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn zipkin_export_reports_server_errors() {
+        use opentelemetry_sdk::trace::SpanExporter;
+        use std::future::Future;
+        use std::task::{Context, Poll, Waker};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+        let exporter = ZipkinExporter::new(format!("{}/api/v2/spans", server.uri())).unwrap();
+
+        // Export outside any runtime, as the batch processor's thread does.
+        // The request blocks, so the future is ready on the first poll.
+        let result = std::thread::spawn(move || {
+            let export = std::pin::pin!(exporter.export(Vec::new()));
+            export.poll(&mut Context::from_waker(Waker::noop()))
+        })
+        .join()
+        .unwrap();
+
+        assert!(matches!(result, Poll::Ready(Err(_))));
+    }
+}
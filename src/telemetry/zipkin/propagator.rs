@@ -0,0 +1,342 @@
+use std::env;
+use std::sync::LazyLock;
+
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::propagation::{text_map_propagator::FieldIter, Extractor, Injector};
+use opentelemetry::trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState};
+use opentelemetry::Context;
+
+/// Single-header format: `b3: TRACE_ID-SPAN_ID-SAMPLED-PARENT_SPAN_ID`
+pub const B3_SINGLE_HEADER: &str = "b3";
+pub const B3_TRACE_ID_HEADER: &str = "x-b3-traceid";
+pub const B3_SPAN_ID_HEADER: &str = "x-b3-spanid";
+pub const B3_PARENT_SPAN_ID_HEADER: &str = "x-b3-parentspanid";
+pub const B3_SAMPLED_HEADER: &str = "x-b3-sampled";
+pub const B3_FLAGS_HEADER: &str = "x-b3-flags";
+
+static SINGLE_FIELDS: LazyLock<[String; 1]> = LazyLock::new(|| [B3_SINGLE_HEADER.to_string()]);
+
+static MULTI_FIELDS: LazyLock<[String; 3]> = LazyLock::new(|| {
+    [
+        B3_TRACE_ID_HEADER.to_string(),
+        B3_SPAN_ID_HEADER.to_string(),
+        B3_SAMPLED_HEADER.to_string(),
+    ]
+});
+
+static ALL_FIELDS: LazyLock<[String; 4]> = LazyLock::new(|| {
+    [
+        B3_SINGLE_HEADER.to_string(),
+        B3_TRACE_ID_HEADER.to_string(),
+        B3_SPAN_ID_HEADER.to_string(),
+        B3_SAMPLED_HEADER.to_string(),
+    ]
+});
+
+/// Headers written on injection; both formats are always read
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum B3Encoding {
+    /// `X-B3-TraceId`, `X-B3-SpanId` and `X-B3-Sampled`
+    #[default]
+    Multiple,
+    /// `b3`
+    Single,
+    SingleAndMultiple,
+}
+
+impl B3Encoding {
+    /// From `OTEL_PROPAGATORS`: `b3` selects the single header, `b3multi` the
+    /// multiple headers, both select both; multiple headers otherwise
+    pub fn from_env() -> Self {
+        env::var("OTEL_PROPAGATORS")
+            .map(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    fn parse(propagators: &str) -> Self {
+        let names: Vec<&str> = propagators.split(',').map(str::trim).collect();
+        match (names.contains(&"b3"), names.contains(&"b3multi")) {
+            (true, true) => Self::SingleAndMultiple,
+            (true, false) => Self::Single,
+            _ => Self::Multiple,
+        }
+    }
+}
+
+/// Propagator for Zipkin's B3 headers.
+///
+/// Extraction prefers the single `b3` header and falls back to the `X-B3-*`
+/// headers. Trace IDs may be 16 or 32 hex characters. The debug flag (`d`
+/// or `X-B3-Flags: 1`) counts as sampled; the parent span ID is ignored.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct B3Propagator {
+    encoding: B3Encoding,
+}
+
+impl B3Propagator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_encoding(encoding: B3Encoding) -> Self {
+        Self { encoding }
+    }
+}
+
+impl TextMapPropagator for B3Propagator {
+    fn inject_context(&self, cx: &Context, injector: &mut dyn Injector) {
+        let span = cx.span();
+        let span_context = span.span_context();
+        if !span_context.is_valid() {
+            return;
+        }
+
+        let sampled = if span_context.is_sampled() { "1" } else { "0" };
+        if self.encoding != B3Encoding::Multiple {
+            injector.set(B3_SINGLE_HEADER, format_single_header(span_context));
+        }
+        if self.encoding != B3Encoding::Single {
+            injector.set(B3_TRACE_ID_HEADER, span_context.trace_id().to_string());
+            injector.set(B3_SPAN_ID_HEADER, span_context.span_id().to_string());
+            injector.set(B3_SAMPLED_HEADER, sampled.to_string());
+        }
+    }
+
+    fn extract_with_context(&self, cx: &Context, extractor: &dyn Extractor) -> Context {
+        extractor
+            .get(B3_SINGLE_HEADER)
+            .and_then(parse_single_header)
+            .or_else(|| extract_multi(extractor))
+            .map(|span_context| cx.with_remote_span_context(span_context))
+            .unwrap_or_else(|| cx.clone())
+    }
+
+    fn fields(&self) -> FieldIter<'_> {
+        match self.encoding {
+            B3Encoding::Multiple => FieldIter::new(MULTI_FIELDS.as_ref()),
+            B3Encoding::Single => FieldIter::new(SINGLE_FIELDS.as_ref()),
+            B3Encoding::SingleAndMultiple => FieldIter::new(ALL_FIELDS.as_ref()),
+        }
+    }
+}
+
+/// Render a span context as a `b3` value
+pub fn format_single_header(span_context: &SpanContext) -> String {
+    format!(
+        "{}-{}-{}",
+        span_context.trace_id(),
+        span_context.span_id(),
+        u8::from(span_context.is_sampled())
+    )
+}
+
+/// Parse a `b3` value into a remote span context.
+///
+/// A bare sampling decision (e.g. `0`) carries no IDs and yields `None`.
+pub fn parse_single_header(value: &str) -> Option<SpanContext> {
+    let mut parts = value.trim().split('-');
+    let trace_id = parse_trace_id(parts.next()?)?;
+    let span_id = parse_span_id(parts.next()?)?;
+    let sampled = match parts.next() {
+        None => false,
+        Some(flag) => parse_sampled(flag)?,
+    };
+    if let Some(parent_span_id) = parts.next() {
+        parse_span_id(parent_span_id)?;
+    }
+    if parts.next().is_some() {
+        return None;
+    }
+
+    span_context(trace_id, span_id, sampled)
+}
+
+fn extract_multi(extractor: &dyn Extractor) -> Option<SpanContext> {
+    let trace_id = parse_trace_id(extractor.get(B3_TRACE_ID_HEADER)?.trim())?;
+    let span_id = parse_span_id(extractor.get(B3_SPAN_ID_HEADER)?.trim())?;
+    let debug = extractor
+        .get(B3_FLAGS_HEADER)
+        .is_some_and(|flags| flags.trim() == "1");
+    let sampled = match extractor.get(B3_SAMPLED_HEADER) {
+        Some(sampled) => parse_sampled(sampled.trim())?,
+        None => false,
+    };
+
+    span_context(trace_id, span_id, sampled || debug)
+}
+
+fn span_context(trace_id: TraceId, span_id: SpanId, sampled: bool) -> Option<SpanContext> {
+    let trace_flags = if sampled {
+        TraceFlags::SAMPLED
+    } else {
+        TraceFlags::default()
+    };
+
+    let span_context =
+        SpanContext::new(trace_id, span_id, trace_flags, true, TraceState::default());
+    span_context.is_valid().then_some(span_context)
+}
+
+/// 16 or 32 hex characters; 64-bit IDs are zero-extended
+fn parse_trace_id(value: &str) -> Option<TraceId> {
+    if value.len() != 16 && value.len() != 32 {
+        return None;
+    }
+    u128::from_str_radix(value, 16).ok().map(TraceId::from)
+}
+
+fn parse_span_id(value: &str) -> Option<SpanId> {
+    if value.len() != 16 {
+        return None;
+    }
+    u64::from_str_radix(value, 16).ok().map(SpanId::from)
+}
+
+/// `1`, `0`, `d` (debug), or the legacy `true` / `false`
+fn parse_sampled(value: &str) -> Option<bool> {
+    match value {
+        "1" | "d" | "true" => Some(true),
+        "0" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const TRACE_ID: &str = "80f198ee56343ba864fe8b2a57d3eff7";
+    const SPAN_ID: &str = "e457b5a2e4d86bd1";
+
+    fn extract(headers: &[(&str, &str)]) -> SpanContext {
+        let carrier: HashMap<String, String> = headers
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let cx = B3Propagator::new().extract(&carrier);
+        cx.span().span_context().clone()
+    }
+
+    fn inject(encoding: B3Encoding, sampled: bool) -> HashMap<String, String> {
+        let flags = if sampled { "1" } else { "0" };
+        let sc = parse_single_header(&format!("{}-{}-{}", TRACE_ID, SPAN_ID, flags)).unwrap();
+        let cx = Context::new().with_remote_span_context(sc);
+        let mut carrier = HashMap::new();
+
+        B3Propagator::with_encoding(encoding).inject_context(&cx, &mut carrier);
+        carrier
+    }
+
+    #[test]
+    fn parses_single_header() {
+        let sc =
+            parse_single_header(&format!("{}-{}-1-05e3ac9a4f6e3b90", TRACE_ID, SPAN_ID)).unwrap();
+
+        assert_eq!(sc.trace_id().to_string(), TRACE_ID);
+        assert_eq!(sc.span_id().to_string(), SPAN_ID);
+        assert!(sc.is_sampled());
+        assert!(sc.is_remote());
+    }
+
+    #[test]
+    fn parses_single_header_sampling_states() {
+        let parse =
+            |flags: &str| parse_single_header(&format!("{}-{}{}", TRACE_ID, SPAN_ID, flags));
+
+        assert!(parse("-d").unwrap().is_sampled());
+        assert!(!parse("-0").unwrap().is_sampled());
+        assert!(!parse("").unwrap().is_sampled());
+        assert!(parse("-x").is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_single_headers() {
+        assert!(parse_single_header("0").is_none());
+        assert!(parse_single_header("").is_none());
+        assert!(parse_single_header(&format!("{}-abc-1", TRACE_ID)).is_none());
+        assert!(parse_single_header(&format!("{}-{}-1-bad", TRACE_ID, SPAN_ID)).is_none());
+        assert!(parse_single_header(&format!("0000000000000000-{}-1", SPAN_ID)).is_none());
+    }
+
+    #[test]
+    fn accepts_64_bit_trace_ids() {
+        let sc = parse_single_header(&format!("64fe8b2a57d3eff7-{}-1", SPAN_ID)).unwrap();
+
+        assert_eq!(
+            sc.trace_id().to_string(),
+            "000000000000000064fe8b2a57d3eff7"
+        );
+    }
+
+    #[test]
+    fn extracts_multiple_headers() {
+        let sc = extract(&[
+            (B3_TRACE_ID_HEADER, TRACE_ID),
+            (B3_SPAN_ID_HEADER, SPAN_ID),
+            (B3_PARENT_SPAN_ID_HEADER, "05e3ac9a4f6e3b90"),
+            (B3_SAMPLED_HEADER, "1"),
+        ]);
+
+        assert_eq!(sc.trace_id().to_string(), TRACE_ID);
+        assert!(sc.is_sampled());
+    }
+
+    #[test]
+    fn debug_flag_counts_as_sampled() {
+        let sc = extract(&[
+            (B3_TRACE_ID_HEADER, TRACE_ID),
+            (B3_SPAN_ID_HEADER, SPAN_ID),
+            (B3_FLAGS_HEADER, "1"),
+        ]);
+
+        assert!(sc.is_sampled());
+    }
+
+    #[test]
+    fn single_header_wins_over_multiple() {
+        let sc = extract(&[
+            (B3_SINGLE_HEADER, &format!("{}-{}-0", TRACE_ID, SPAN_ID)),
+            (B3_TRACE_ID_HEADER, "0af7651916cd43dd8448eb211c80319c"),
+            (B3_SPAN_ID_HEADER, "b7ad6b7169203331"),
+        ]);
+
+        assert_eq!(sc.trace_id().to_string(), TRACE_ID);
+        assert!(!sc.is_sampled());
+    }
+
+    #[test]
+    fn extract_without_headers_is_empty() {
+        assert!(!extract(&[]).is_valid());
+        assert!(!extract(&[(B3_TRACE_ID_HEADER, TRACE_ID)]).is_valid());
+    }
+
+    #[test]
+    fn injects_per_encoding() {
+        let multi = inject(B3Encoding::Multiple, true);
+        assert_eq!(multi[B3_TRACE_ID_HEADER], TRACE_ID);
+        assert_eq!(multi[B3_SPAN_ID_HEADER], SPAN_ID);
+        assert_eq!(multi[B3_SAMPLED_HEADER], "1");
+        assert!(!multi.contains_key(B3_SINGLE_HEADER));
+
+        let single = inject(B3Encoding::Single, false);
+        assert_eq!(
+            single[B3_SINGLE_HEADER],
+            format!("{}-{}-0", TRACE_ID, SPAN_ID)
+        );
+        assert_eq!(single.len(), 1);
+
+        assert_eq!(inject(B3Encoding::SingleAndMultiple, true).len(), 4);
+    }
+
+    #[test]
+    fn encoding_follows_otel_propagators() {
+        assert_eq!(B3Encoding::parse("tracecontext,b3"), B3Encoding::Single);
+        assert_eq!(B3Encoding::parse("b3multi"), B3Encoding::Multiple);
+        assert_eq!(
+            B3Encoding::parse("b3, b3multi"),
+            B3Encoding::SingleAndMultiple
+        );
+        assert_eq!(B3Encoding::parse("tracecontext"), B3Encoding::Multiple);
+    }
+}